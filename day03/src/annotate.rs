@@ -0,0 +1,216 @@
+use std::io::IsTerminal;
+
+const RESET: &str = "\x1b[0m";
+const GREEN: &str = "\x1b[1;32m";
+const YELLOW: &str = "\x1b[2;33m";
+const CYAN: &str = "\x1b[36m";
+const RED: &str = "\x1b[4;31m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionKind {
+    Mul(u32, u32),
+    Do,
+    Dont,
+    /// Something that starts like `mul(` but isn't a valid instruction, e.g. `mul(3,7]` or `mul( 2 , 4 )`.
+    NearMiss,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub kind: InstructionKind,
+    /// Byte offset into the memory dump.
+    pub offset: usize,
+    /// Length in bytes of the matched text.
+    pub len: usize,
+    /// Whether `mul` instructions were enabled at this point (after applying a `do()`/`don't()`).
+    pub enabled: bool,
+}
+
+impl Instruction {
+    pub fn product(&self) -> Option<u32> {
+        match self.kind {
+            InstructionKind::Mul(a, b) => Some(a * b),
+            _ => None,
+        }
+    }
+}
+
+/// Walks the memory dump and records every recognised instruction along with near-misses.
+/// Numbers are limited to 1-3 digits as per the puzzle statement.
+/// This is the only tokenizer, the solvers add up the products it finds too.
+pub fn scan_instructions(input: &str) -> Vec<Instruction> {
+    let bytes = input.as_bytes();
+    let mut instructions = vec![];
+    let mut enabled = true;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+
+        if rest.starts_with(b"do()") {
+            enabled = true;
+            instructions.push(Instruction {
+                kind: InstructionKind::Do,
+                offset: i,
+                len: 4,
+                enabled,
+            });
+            i += 4;
+        } else if rest.starts_with(b"don't()") {
+            enabled = false;
+            instructions.push(Instruction {
+                kind: InstructionKind::Dont,
+                offset: i,
+                len: 7,
+                enabled,
+            });
+            i += 7;
+        } else if rest.starts_with(b"mul") {
+            let (kind, len) = match parse_mul(rest) {
+                Ok((a, b, len)) => (InstructionKind::Mul(a, b), len),
+                // A bare `mul` is just a word, not an attempted instruction.
+                Err(len) if len <= 3 => {
+                    i += len;
+                    continue;
+                }
+                Err(len) => (InstructionKind::NearMiss, len),
+            };
+            instructions.push(Instruction {
+                kind,
+                offset: i,
+                len,
+                enabled,
+            });
+            i += len;
+        } else {
+            i += 1;
+        }
+    }
+
+    instructions
+}
+
+/// Parses `mul(a,b)` at the start of `bytes`.
+/// On failure returns the number of bytes that did match so the near-miss can be highlighted.
+fn parse_mul(bytes: &[u8]) -> Result<(u32, u32, usize), usize> {
+    let mut pos = 3;
+    if bytes.get(pos) != Some(&b'(') {
+        return Err(pos);
+    }
+    pos += 1;
+
+    let a = parse_number(bytes, &mut pos).ok_or(pos)?;
+    if bytes.get(pos) != Some(&b',') {
+        return Err(pos);
+    }
+    pos += 1;
+
+    let b = parse_number(bytes, &mut pos).ok_or(pos)?;
+    if bytes.get(pos) != Some(&b')') {
+        return Err(pos);
+    }
+    pos += 1;
+
+    Ok((a, b, pos))
+}
+
+fn parse_number(bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    let start = *pos;
+    let mut num = 0;
+    while *pos - start < 3 {
+        match bytes.get(*pos) {
+            Some(c) if c.is_ascii_digit() => {
+                num = num * 10 + (c - b'0') as u32;
+                *pos += 1;
+            }
+            _ => break,
+        }
+    }
+
+    if *pos == start {
+        None
+    } else {
+        Some(num)
+    }
+}
+
+/// Renders the memory dump with every instruction highlighted.
+/// Uses ANSI colours when `colour` is set, otherwise wraps instructions in bracketed markers:
+/// `[+..]` counted, `[-..]` disabled, `[=..]` do/don't and `[?..]` near-miss.
+pub fn render_annotated(input: &str, instructions: &[Instruction], colour: bool) -> String {
+    let mut out = String::with_capacity(input.len() * 2);
+    let mut last = 0;
+
+    for ins in instructions.iter() {
+        out.push_str(&input[last..ins.offset]);
+        let text = &input[ins.offset..ins.offset + ins.len];
+
+        let (ansi, marker) = match ins.kind {
+            InstructionKind::Mul(_, _) if ins.enabled => (GREEN, '+'),
+            InstructionKind::Mul(_, _) => (YELLOW, '-'),
+            InstructionKind::Do | InstructionKind::Dont => (CYAN, '='),
+            InstructionKind::NearMiss => (RED, '?'),
+        };
+
+        if colour {
+            out.push_str(&format!("{ansi}{text}{RESET}"));
+        } else {
+            out.push_str(&format!("[{marker}{text}]"));
+        }
+
+        last = ins.offset + ins.len;
+    }
+    out.push_str(&input[last..]);
+
+    out
+}
+
+pub fn render_table(input: &str, instructions: &[Instruction]) -> String {
+    let mut out = format!(
+        "{:>8}  {:<16}  {:>8}  {}\n",
+        "offset", "instruction", "product", "enabled"
+    );
+
+    for ins in instructions.iter() {
+        let text = &input[ins.offset..ins.offset + ins.len];
+        let product = match ins.product() {
+            Some(p) => p.to_string(),
+            None => String::from("-"),
+        };
+        let enabled = match ins.kind {
+            InstructionKind::NearMiss => "ignored",
+            _ if ins.enabled => "yes",
+            _ => "no",
+        };
+        out.push_str(&format!(
+            "{:>8}  {:<16}  {:>8}  {}\n",
+            ins.offset, text, product, enabled
+        ));
+    }
+
+    let all = sum_products(instructions, false);
+    let enabled = sum_products(instructions, true);
+    out.push_str(&format!(
+        "\nSum of all mul: {all}\nSum of enabled mul: {enabled}\n"
+    ));
+
+    out
+}
+
+pub fn print_annotated(input: &str) {
+    let instructions = scan_instructions(input);
+    let colour = std::io::stdout().is_terminal();
+
+    println!("{}", render_annotated(input, &instructions, colour));
+    println!();
+    print!("{}", render_table(input, &instructions));
+}
+
+/// Sum of every `mul` product, or only the enabled ones if `only_enabled` is set.
+pub fn sum_products(instructions: &[Instruction], only_enabled: bool) -> u32 {
+    instructions
+        .iter()
+        .filter(|i| i.enabled || !only_enabled)
+        .filter_map(|i| i.product())
+        .sum()
+}
@@ -2,11 +2,21 @@
 
 use generate_aoc_day::generate_day;
 
+mod annotate;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 3);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("annotate") => annotate::print_annotated(&mode_input()),
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
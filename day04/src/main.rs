@@ -3,10 +3,48 @@
 use generate_aoc_day::generate_day;
 
 mod solution;
+mod visualise;
+mod word_search;
 
 // Replace with desired year/day
 generate_day!(2024, 4);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("search") => {
+            let words = std::env::var("words").unwrap_or_else(|_| String::from("XMAS"));
+            let words: Vec<&str> = words.split(',').collect();
+            let directions = std::env::var("directions")
+                .unwrap_or_else(|_| String::from("all"))
+                .parse()
+                .unwrap_or_else(|e| panic!("Invalid directions: {e}"));
+            word_search::print_search(&mode_grid(), &words, &directions);
+        }
+        Ok("x_search") => {
+            let word = std::env::var("word").unwrap_or_else(|_| String::from("MAS"));
+            word_search::print_x_search(&mode_grid(), &word)
+                .unwrap_or_else(|e| fail(&format!("Invalid word: {e}")));
+        }
+        Ok("visualise") => {
+            let part_two = std::env::var("part").as_deref() == Ok("part2");
+            let colour = std::env::var("colour").is_ok();
+            visualise::print_visualisation(&mode_grid(), part_two, colour);
+        }
+        _ => handle_day(),
+    }
+}
+
+/// Reports an invalid setting and exits.
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+fn mode_grid() -> Vec<Vec<char>> {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
+        .lines()
+        .map(|l| l.chars().collect())
+        .collect()
 }
@@ -0,0 +1,81 @@
+use crate::word_search::{find_words, find_x_pattern, DirectionSet};
+
+const RESET: &str = "\x1b[0m";
+/// Colours for cells in 1, 2 and 3+ matches respectively.
+const OVERLAP_COLOURS: [&str; 3] = ["\x1b[32m", "\x1b[33m", "\x1b[31m"];
+
+/// Counts how many matches each cell of the grid participates in.
+pub fn match_counts(
+    grid: &[Vec<char>],
+    matches: impl Iterator<Item = Vec<(usize, usize)>>,
+) -> Vec<Vec<usize>> {
+    let mut counts: Vec<Vec<usize>> = grid.iter().map(|row| vec![0; row.len()]).collect();
+    for cells in matches {
+        for (x, y) in cells {
+            counts[y][x] += 1;
+        }
+    }
+    counts
+}
+
+/// Counts per cell for the matches of the given part (`XMAS` in all directions, or the `MAS` X-pattern).
+pub fn part_counts(grid: &[Vec<char>], part_two: bool) -> Vec<Vec<usize>> {
+    if part_two {
+        match_counts(
+            grid,
+            find_x_pattern(grid, "MAS")
+                .expect("MAS has an odd length")
+                .iter()
+                .map(|m| m.cells()),
+        )
+    } else {
+        match_counts(
+            grid,
+            find_words(grid, &["XMAS"], &DirectionSet::All)
+                .iter()
+                .map(|m| m.cells()),
+        )
+    }
+}
+
+/// Renders the grid like the puzzle statement, with letters that aren't part of any match replaced by `.`.
+/// With `colour` set, matched letters are coloured by how many matches they overlap in.
+pub fn render_matches(grid: &[Vec<char>], counts: &[Vec<usize>], colour: bool) -> String {
+    let mut out = String::new();
+    for (row, row_counts) in grid.iter().zip(counts.iter()) {
+        for (c, count) in row.iter().zip(row_counts.iter()) {
+            match count {
+                0 => out.push('.'),
+                n if colour => {
+                    let ansi = OVERLAP_COLOURS[(n - 1).min(OVERLAP_COLOURS.len() - 1)];
+                    out.push_str(&format!("{ansi}{c}{RESET}"));
+                }
+                _ => out.push(*c),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders the number of matches each cell participates in, `.` for none and `+` for more than 9.
+pub fn render_counts(counts: &[Vec<usize>]) -> String {
+    let mut out = String::new();
+    for row in counts.iter() {
+        for count in row.iter() {
+            out.push(match count {
+                0 => '.',
+                n if *n > 9 => '+',
+                n => char::from_digit(*n as u32, 10).unwrap(),
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn print_visualisation(grid: &[Vec<char>], part_two: bool, colour: bool) {
+    let counts = part_counts(grid, part_two);
+    println!("{}", render_matches(grid, &counts, colour));
+    println!("{}", render_counts(&counts));
+}
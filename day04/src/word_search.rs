@@ -0,0 +1,316 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Direction {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Direction {
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self { dx, dy }
+    }
+}
+
+pub const UP: Direction = Direction::new(0, -1);
+pub const DOWN: Direction = Direction::new(0, 1);
+pub const LEFT: Direction = Direction::new(-1, 0);
+pub const RIGHT: Direction = Direction::new(1, 0);
+pub const UP_LEFT: Direction = Direction::new(-1, -1);
+pub const UP_RIGHT: Direction = Direction::new(1, -1);
+pub const DOWN_LEFT: Direction = Direction::new(-1, 1);
+pub const DOWN_RIGHT: Direction = Direction::new(1, 1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectionSet {
+    Orthogonal,
+    Diagonal,
+    All,
+    Custom(Vec<Direction>),
+}
+
+impl FromStr for DirectionSet {
+    type Err = String;
+
+    /// Accepts `orthogonal`, `diagonal`, `all` or a custom list of `dx,dy` pairs separated by `;`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "orthogonal" => Ok(DirectionSet::Orthogonal),
+            "diagonal" => Ok(DirectionSet::Diagonal),
+            "all" => Ok(DirectionSet::All),
+            custom => custom
+                .split(';')
+                .map(|pair| {
+                    let (dx, dy) = pair
+                        .split_once(',')
+                        .ok_or(format!("Invalid direction '{pair}', expected 'dx,dy'"))?;
+                    let dx = dx
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid dx '{dx}'"))?;
+                    let dy = dy
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid dy '{dy}'"))?;
+                    if dx == 0 && dy == 0 {
+                        return Err(String::from("Direction 0,0 is not allowed"));
+                    }
+                    Ok(Direction::new(dx, dy))
+                })
+                .collect::<Result<Vec<Direction>, String>>()
+                .map(DirectionSet::Custom),
+        }
+    }
+}
+
+impl DirectionSet {
+    pub fn directions(&self) -> Vec<Direction> {
+        match self {
+            DirectionSet::Orthogonal => vec![UP, DOWN, LEFT, RIGHT],
+            DirectionSet::Diagonal => vec![UP_LEFT, UP_RIGHT, DOWN_LEFT, DOWN_RIGHT],
+            DirectionSet::All => vec![
+                UP, DOWN, LEFT, RIGHT, UP_LEFT, UP_RIGHT, DOWN_LEFT, DOWN_RIGHT,
+            ],
+            DirectionSet::Custom(dirs) => {
+                // A direction listed twice would find every match along it twice.
+                let mut unique = vec![];
+                for dir in dirs.iter() {
+                    if !unique.contains(dir) {
+                        unique.push(*dir);
+                    }
+                }
+                unique
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordMatch {
+    pub word: String,
+    pub x: usize,
+    pub y: usize,
+    pub direction: Direction,
+}
+
+impl WordMatch {
+    /// The grid positions covered by the match, in word order.
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        (0..self.word.chars().count() as isize)
+            .map(|i| {
+                (
+                    (self.x as isize + self.direction.dx * i) as usize,
+                    (self.y as isize + self.direction.dy * i) as usize,
+                )
+            })
+            .collect()
+    }
+}
+
+/// An X-shaped match, i.e. the word appearing on both diagonals crossing at (`x`, `y`).
+/// The directions tell which way the word reads along each diagonal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XMatch {
+    pub word: String,
+    pub x: usize,
+    pub y: usize,
+    /// Reading direction along the top-left/bottom-right diagonal.
+    pub main_diagonal: Direction,
+    /// Reading direction along the top-right/bottom-left diagonal.
+    pub anti_diagonal: Direction,
+}
+
+impl XMatch {
+    /// The grid positions covered by the match, centre included once.
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let half = (self.word.chars().count() / 2) as isize;
+        let mut cells = vec![(self.x, self.y)];
+        for i in 1..=half {
+            for (dx, dy) in [(i, i), (-i, -i), (i, -i), (-i, i)] {
+                cells.push((
+                    (self.x as isize + dx) as usize,
+                    (self.y as isize + dy) as usize,
+                ));
+            }
+        }
+        cells
+    }
+}
+
+fn get(grid: &[Vec<char>], x: isize, y: isize) -> Option<char> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+    grid.get(y as usize)?.get(x as usize).copied()
+}
+
+fn matches_at(grid: &[Vec<char>], word: &[char], x: isize, y: isize, dir: Direction) -> bool {
+    word.iter()
+        .enumerate()
+        .all(|(i, c)| get(grid, x + dir.dx * i as isize, y + dir.dy * i as isize) == Some(*c))
+}
+
+/// Finds every occurrence of every word starting at any cell and reading in any of the given directions.
+/// Rows may have differing lengths.
+pub fn find_words(grid: &[Vec<char>], words: &[&str], directions: &DirectionSet) -> Vec<WordMatch> {
+    let directions = directions.directions();
+    let words: Vec<(&str, Vec<char>)> = words
+        .iter()
+        .filter(|w| !w.is_empty())
+        .map(|w| (*w, w.chars().collect()))
+        .collect();
+
+    let mut matches = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            for (word, chars) in words.iter() {
+                if chars[0] != *c {
+                    continue;
+                }
+
+                for dir in directions.iter() {
+                    if matches_at(grid, chars, x as isize, y as isize, *dir) {
+                        matches.push(WordMatch {
+                            word: word.to_string(),
+                            x,
+                            y,
+                            direction: *dir,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+/// Finds every cell where `word` appears on both diagonals crossing through it (either way around).
+/// `word` must have an odd length so it has a middle letter to cross at.
+pub fn find_x_pattern(grid: &[Vec<char>], word: &str) -> Result<Vec<XMatch>, String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len().is_multiple_of(2) {
+        return Err(format!(
+            "X-pattern word must have an odd length, got '{word}'"
+        ));
+    }
+
+    let half = (chars.len() / 2) as isize;
+    let middle = chars[chars.len() / 2];
+
+    let mut matches = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if *c != middle {
+                continue;
+            }
+
+            let (x, y) = (x as isize, y as isize);
+            let diagonal = |dir: Direction| {
+                matches_at(grid, &chars, x - dir.dx * half, y - dir.dy * half, dir)
+            };
+
+            let main = [DOWN_RIGHT, UP_LEFT].into_iter().find(|d| diagonal(*d));
+            let anti = [DOWN_LEFT, UP_RIGHT].into_iter().find(|d| diagonal(*d));
+
+            if let (Some(main_diagonal), Some(anti_diagonal)) = (main, anti) {
+                matches.push(XMatch {
+                    word: word.to_string(),
+                    x: x as usize,
+                    y: y as usize,
+                    main_diagonal,
+                    anti_diagonal,
+                });
+            }
+        }
+    }
+
+    Ok(matches)
+}
+
+pub fn print_search(grid: &[Vec<char>], words: &[&str], directions: &DirectionSet) {
+    let matches = find_words(grid, words, directions);
+    for m in matches.iter() {
+        println!(
+            "{} at ({}, {}) direction ({}, {})",
+            m.word, m.x, m.y, m.direction.dx, m.direction.dy
+        );
+    }
+    println!("{} matches", matches.len());
+}
+
+pub fn print_x_search(grid: &[Vec<char>], word: &str) -> Result<(), String> {
+    let matches = find_x_pattern(grid, word)?;
+    for m in matches.iter() {
+        println!(
+            "{} crossing at ({}, {}) diagonals ({}, {}) and ({}, {})",
+            m.word,
+            m.x,
+            m.y,
+            m.main_diagonal.dx,
+            m.main_diagonal.dy,
+            m.anti_diagonal.dx,
+            m.anti_diagonal.dy
+        );
+    }
+    println!("{} matches", matches.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|r| r.chars().collect()).collect()
+    }
+
+    #[test]
+    fn x_pattern_reads_either_way_along_each_diagonal() {
+        let grid = grid(&["M.S", ".A.", "M.S"]);
+        let matches = find_x_pattern(&grid, "MAS").unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].x, matches[0].y), (1, 1));
+        assert_eq!(matches[0].main_diagonal, DOWN_RIGHT);
+        assert_eq!(matches[0].anti_diagonal, UP_RIGHT);
+    }
+
+    #[test]
+    fn x_pattern_needs_both_diagonals() {
+        let grid = grid(&["M.M", ".A.", "S.M"]);
+        assert!(find_x_pattern(&grid, "MAS").unwrap().is_empty());
+    }
+
+    #[test]
+    fn x_pattern_rejects_even_and_empty_words() {
+        let grid = grid(&["MASS"]);
+        assert!(find_x_pattern(&grid, "MASS").is_err());
+        assert!(find_x_pattern(&grid, "").is_err());
+    }
+
+    #[test]
+    fn custom_directions_parse_and_search() {
+        let directions: DirectionSet = "1,0; 0,1".parse().unwrap();
+        let grid = grid(&["AB", "B."]);
+        let matches = find_words(&grid, &["AB"], &directions);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].direction, RIGHT);
+        assert_eq!(matches[1].direction, DOWN);
+    }
+
+    #[test]
+    fn duplicate_custom_directions_count_once() {
+        let directions: DirectionSet = "1,0;1,0".parse().unwrap();
+        let grid = grid(&["XMAS"]);
+        assert_eq!(find_words(&grid, &["XMAS"], &directions).len(), 1);
+    }
+
+    #[test]
+    fn invalid_custom_directions_are_rejected() {
+        assert!("0,0".parse::<DirectionSet>().is_err());
+        assert!("1".parse::<DirectionSet>().is_err());
+        assert!("x,1".parse::<DirectionSet>().is_err());
+    }
+}
@@ -2,11 +2,22 @@
 
 use generate_aoc_day::generate_day;
 
+mod ordering;
+mod report;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 5);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("report") => report::print_report(&solution::parse(&mode_input())),
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
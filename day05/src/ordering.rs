@@ -0,0 +1,172 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Display,
+};
+
+/// The page ordering rules, `X|Y` meaning that `X` must be printed at some point before `Y`.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    before_map: HashMap<u32, HashSet<u32>>,
+}
+
+/// A broken rule `before|after`, i.e. `after` was found at an earlier position than `before`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub before: u32,
+    pub after: u32,
+    pub before_index: usize,
+    pub after_index: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopologicalOrder {
+    pub pages: Vec<u32>,
+    /// Whether the rules restricted to the update allow exactly one ordering.
+    pub unique: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// The pages making up the cycle, each having to come before the next (and the last before the first).
+    pub pages: Vec<u32>,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pages = self
+            .pages
+            .iter()
+            .chain(self.pages.first())
+            .map(|p| p.to_string())
+            .collect::<Vec<String>>()
+            .join(" -> ");
+        write!(f, "Ordering rules contain a cycle: {pages}")
+    }
+}
+
+impl RuleSet {
+    pub fn add_rule(&mut self, before: u32, after: u32) {
+        self.before_map.entry(before).or_default().insert(after);
+    }
+
+    /// Whether there is a rule stating that `a` must come before `b`.
+    pub fn must_precede(&self, a: u32, b: u32) -> bool {
+        self.before_map
+            .get(&a)
+            .map(|afters| afters.contains(&b))
+            .unwrap_or(false)
+    }
+
+    /// Whether no rule is broken by the order of pages in `update`.
+    pub fn is_ordered(&self, update: &[u32]) -> bool {
+        update
+            .iter()
+            .enumerate()
+            .all(|(i, a)| update[i + 1..].iter().all(|b| !self.must_precede(*b, *a)))
+    }
+
+    /// Every rule broken by `update`, ordered by the position of the page that came too early.
+    pub fn violations(&self, update: &[u32]) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (after_index, after) in update.iter().enumerate() {
+            for (before_index, before) in update.iter().enumerate().skip(after_index + 1) {
+                if self.must_precede(*before, *after) {
+                    violations.push(Violation {
+                        before: *before,
+                        after: *after,
+                        before_index,
+                        after_index,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Sorts the pages of `update` using Kahn's algorithm on the rules restricted to those pages.
+    /// Whenever several pages are free to go next the one appearing first in `update` is picked,
+    /// so the result is deterministic and leaves already correct updates untouched.
+    pub fn topological_order(&self, update: &[u32]) -> Result<TopologicalOrder, CycleError> {
+        let edges = self.edges(update);
+
+        let mut in_degree = vec![0; update.len()];
+        for targets in edges.iter() {
+            for &to in targets.iter() {
+                in_degree[to] += 1;
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = (0..update.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut pages = Vec::with_capacity(update.len());
+        let mut unique = true;
+
+        while let Some(index) = ready.pop_first() {
+            if !ready.is_empty() {
+                unique = false;
+            }
+
+            pages.push(update[index]);
+            for &to in edges[index].iter() {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    ready.insert(to);
+                }
+            }
+        }
+
+        if pages.len() < update.len() {
+            let remaining: Vec<bool> = in_degree.iter().map(|&d| d > 0).collect();
+            return Err(CycleError {
+                pages: find_cycle(&edges, &remaining)
+                    .into_iter()
+                    .map(|i| update[i])
+                    .collect(),
+            });
+        }
+
+        Ok(TopologicalOrder { pages, unique })
+    }
+
+    /// Adjacency lists between indices of `update`, `i -> j` if page `i` must precede page `j`.
+    fn edges(&self, update: &[u32]) -> Vec<Vec<usize>> {
+        update
+            .iter()
+            .map(|a| {
+                update
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, b)| self.must_precede(*a, **b))
+                    .map(|(j, _)| j)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Finds a cycle among the `remaining` nodes, every one of which has an incoming edge from another remaining node.
+fn find_cycle(edges: &[Vec<usize>], remaining: &[bool]) -> Vec<usize> {
+    // Walk backwards along incoming edges, which always exist for remaining nodes, until a node repeats.
+    let mut incoming: Vec<Option<usize>> = vec![None; edges.len()];
+    for (from, targets) in edges.iter().enumerate() {
+        if !remaining[from] {
+            continue;
+        }
+        for &to in targets.iter() {
+            incoming[to] = Some(from);
+        }
+    }
+
+    let start = remaining.iter().position(|&r| r).unwrap();
+    let mut seen_at: HashMap<usize, usize> = HashMap::new();
+    let mut path = vec![];
+    let mut curr = start;
+    while !seen_at.contains_key(&curr) {
+        seen_at.insert(curr, path.len());
+        path.push(curr);
+        curr = incoming[curr].expect("Remaining node without incoming edge");
+    }
+
+    let mut cycle = path[seen_at[&curr]..].to_vec();
+    cycle.reverse();
+    cycle
+}
@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+use crate::{
+    ordering::{CycleError, RuleSet, TopologicalOrder, Violation},
+    solution::Input,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateReport {
+    pub update: Vec<u32>,
+    pub violations: Vec<Violation>,
+    pub corrected: Result<TopologicalOrder, CycleError>,
+}
+
+impl UpdateReport {
+    pub fn new(rules: &RuleSet, update: &[u32]) -> Self {
+        Self {
+            update: update.to_vec(),
+            violations: rules.violations(update),
+            corrected: rules.topological_order(update),
+        }
+    }
+
+    pub fn is_ordered(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn middle_before(&self) -> u32 {
+        self.update[self.update.len() / 2]
+    }
+
+    /// The middle page after fixing the order, `None` if the rules for this update contain a cycle.
+    pub fn middle_after(&self) -> Option<u32> {
+        self.corrected
+            .as_ref()
+            .ok()
+            .map(|order| order.pages[order.pages.len() / 2])
+    }
+}
+
+impl Display for UpdateReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Update {}", join(&self.update))?;
+        if self.is_ordered() {
+            writeln!(f, "  Correctly ordered")?;
+        } else {
+            writeln!(f, "  {} violated rule(s):", self.violations.len())?;
+            for v in self.violations.iter() {
+                writeln!(
+                    f,
+                    "    {}|{} ({} at position {}, {} at position {})",
+                    v.before, v.after, v.before, v.before_index, v.after, v.after_index
+                )?;
+            }
+        }
+
+        match &self.corrected {
+            Ok(order) => {
+                let uniqueness = if order.unique { "unique" } else { "not unique" };
+                writeln!(
+                    f,
+                    "  Corrected order: {} ({uniqueness})",
+                    join(&order.pages)
+                )?;
+            }
+            Err(e) => writeln!(f, "  Unable to correct: {e}")?,
+        }
+
+        let middle_after = self
+            .middle_after()
+            .map(|m| m.to_string())
+            .unwrap_or(String::from("-"));
+        writeln!(
+            f,
+            "  Middle page: {} before, {} after",
+            self.middle_before(),
+            middle_after
+        )
+    }
+}
+
+fn join(pages: &[u32]) -> String {
+    pages
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn build_reports(input: &Input) -> Vec<UpdateReport> {
+    input
+        .updates
+        .iter()
+        .map(|update| UpdateReport::new(&input.rules, update))
+        .collect()
+}
+
+pub fn print_report(input: &Input) {
+    let reports = build_reports(input);
+    for report in reports.iter() {
+        println!("{report}");
+    }
+
+    let incorrect = reports.iter().filter(|r| !r.is_ordered()).count();
+    println!(
+        "{} of {} updates correctly ordered, {} violated rules in total",
+        reports.len() - incorrect,
+        reports.len(),
+        reports.iter().map(|r| r.violations.len()).sum::<usize>()
+    );
+}
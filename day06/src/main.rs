@@ -2,11 +2,45 @@
 
 use generate_aoc_day::generate_day;
 
+mod patrol;
+mod render;
+mod simulation;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 6);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("render") => {
+            let obstruction = std::env::var("obstruction").ok().map(|o| {
+                let (x, y) = o
+                    .split_once(',')
+                    .expect("Obstruction should be given as x,y");
+                let coordinate = |c: &str| {
+                    c.trim()
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid obstruction coordinate '{c}'"))
+                };
+                solution::Pos {
+                    x: coordinate(x),
+                    y: coordinate(y),
+                }
+            });
+            let frames = std::env::var("frames").is_ok();
+            render::print_route(&solution::parse(&mode_input()), obstruction, frames)
+                .unwrap_or_else(|e| solution::fail(e));
+        }
+        Ok("patrol") => match patrol::PatrolReport::new(&solution::parse(&mode_input())) {
+            Ok(report) => println!("{report}"),
+            Err(e) => solution::fail(e),
+        },
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
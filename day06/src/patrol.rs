@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use crate::{
+    simulation::Simulator,
+    solution::{Direction, GuardError, Map, Pos},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardCoverage {
+    pub start: Pos,
+    pub dir: Direction,
+    /// Number of distinct positions the guard visits.
+    pub visited: usize,
+    /// Number of positions visited by this guard and no other.
+    pub exclusive: usize,
+    /// Whether the guard patrols in a loop forever instead of leaving the map.
+    pub looped: bool,
+}
+
+/// Coverage of several guards patrolling at the same time.
+/// A guard turns away from another guard in its way as it would from an obstruction, see [`Simulator::patrol`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatrolReport {
+    pub guards: Vec<GuardCoverage>,
+    /// Number of distinct positions visited by any guard.
+    pub union: usize,
+}
+
+impl PatrolReport {
+    pub fn new(map: &Map) -> Result<Self, GuardError> {
+        let guards = map.guards();
+        if guards.is_empty() {
+            return Err(GuardError::NoGuard);
+        }
+
+        let sim = Simulator::new(map);
+        let starts: Vec<_> = guards
+            .iter()
+            .map(|(pos, dir)| (sim.to_cell(pos), *dir))
+            .collect();
+        let paths = sim.patrol(&starts);
+
+        let mut visitors = vec![0; sim.cell_count()];
+        for path in paths.iter() {
+            for (cell, visited) in path.visited.iter().enumerate() {
+                if *visited {
+                    visitors[cell] += 1;
+                }
+            }
+        }
+
+        let guards = guards
+            .into_iter()
+            .zip(paths.iter())
+            .map(|((start, dir), path)| GuardCoverage {
+                start,
+                dir,
+                visited: path.visited_count(),
+                exclusive: path
+                    .visited
+                    .iter()
+                    .zip(visitors.iter())
+                    .filter(|(visited, count)| **visited && **count == 1)
+                    .count(),
+                looped: path.looped,
+            })
+            .collect();
+
+        Ok(Self {
+            guards,
+            union: visitors.iter().filter(|v| **v > 0).count(),
+        })
+    }
+}
+
+impl Display for PatrolReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, guard) in self.guards.iter().enumerate() {
+            writeln!(
+                f,
+                "Guard {} starting at ({}, {}) facing {:?}: {} positions visited, {} by this guard only{}",
+                i + 1,
+                guard.start.x,
+                guard.start.y,
+                guard.dir,
+                guard.visited,
+                guard.exclusive,
+                if guard.looped { ", patrols in a loop" } else { "" }
+            )?;
+        }
+        write!(f, "{} positions visited by any guard", self.union)
+    }
+}
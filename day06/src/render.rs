@@ -0,0 +1,122 @@
+use crate::{
+    simulation::Simulator,
+    solution::{Direction, Map, Pos, Tile},
+};
+
+const VERTICAL: u8 = 1;
+const HORIZONTAL: u8 = 2;
+
+fn guard_glyph(dir: Direction) -> char {
+    match dir {
+        Direction::Up => '^',
+        Direction::Down => 'v',
+        Direction::Right => '>',
+        Direction::Left => '<',
+    }
+}
+
+/// Renders the map like the puzzle text, with the route drawn as `|` and `-` (`+` where the guard turns or crosses its path)
+/// and each cell set in the `added` obstruction bitmap drawn as `O`. When `guard` is set the guard is drawn at its
+/// current position.
+pub fn render_route(
+    map: &Map,
+    sim: &Simulator,
+    route: &[(usize, Direction)],
+    added: &[bool],
+    guard: Option<(usize, Direction)>,
+) -> String {
+    let mut marks = vec![0; sim.cell_count()];
+    for (cell, dir) in route.iter() {
+        marks[*cell] |= match dir {
+            Direction::Up | Direction::Down => VERTICAL,
+            Direction::Left | Direction::Right => HORIZONTAL,
+        };
+    }
+
+    let mut out = String::new();
+    for (y, row) in map.map.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            let cell = sim.to_cell(&Pos { x, y });
+            let c = match (tile, marks[cell]) {
+                _ if guard.map(|(g, _)| g) == Some(cell) => guard_glyph(guard.unwrap().1),
+                _ if added[cell] => 'O',
+                (Tile::Obstruction, _) => '#',
+                (Tile::GuardStart(d), _) => guard_glyph(*d),
+                (_, VERTICAL) => '|',
+                (_, HORIZONTAL) => '-',
+                (_, 0) => '.',
+                _ => '+',
+            };
+            out.push(c);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Prints the map once per turn of the guard, with the route walked so far.
+fn print_frames(map: &Map, sim: &Simulator, route: &[(usize, Direction)], added: &[bool]) {
+    let mut turn = 0;
+    for (i, (cell, dir)) in route.iter().enumerate() {
+        let is_turn = i > 0 && route[i - 1].0 == *cell;
+        if !is_turn && i + 1 < route.len() {
+            continue;
+        }
+
+        let Pos { x, y } = sim.to_pos(*cell);
+        if is_turn {
+            turn += 1;
+            println!("Turn {turn} at ({x}, {y})");
+        } else {
+            println!("Final position ({x}, {y})");
+        }
+        println!(
+            "{}",
+            render_route(map, sim, &route[..=i], added, Some((*cell, *dir)))
+        );
+    }
+}
+
+/// Prints the guard's route.
+/// Without `obstruction` every obstruction that would cause a loop is marked with `O`,
+/// otherwise the route with only that obstruction added is drawn.
+/// Fails if the map doesn't have exactly one guard or the obstruction is outside the map.
+pub fn print_route(map: &Map, obstruction: Option<Pos>, frames: bool) -> Result<(), String> {
+    let (guard_pos, guard_dir) = map.guard_start().map_err(|e| e.to_string())?;
+    if let Some(Pos { x, y }) = obstruction {
+        if x >= map.width || y >= map.height {
+            return Err(format!(
+                "Obstruction ({x}, {y}) is outside the {}x{} map",
+                map.width, map.height
+            ));
+        }
+    }
+
+    let sim = Simulator::new(map);
+    let start = sim.to_cell(&guard_pos);
+
+    let extra = obstruction.map(|pos| sim.to_cell(&pos));
+    let (route, looped) = sim.route(start, guard_dir, extra);
+    let obstructions = match extra {
+        Some(extra) => vec![extra],
+        None => sim.loop_obstructions(start, guard_dir),
+    };
+
+    let mut added = vec![false; sim.cell_count()];
+    for cell in obstructions.iter() {
+        added[*cell] = true;
+    }
+
+    if frames {
+        print_frames(map, &sim, &route, &added);
+    } else {
+        println!("{}", render_route(map, &sim, &route, &added, None));
+    }
+
+    match extra {
+        Some(_) if looped => println!("The guard gets stuck in a loop"),
+        Some(_) => println!("The guard leaves the map"),
+        None => println!("{} obstructions cause a loop", obstructions.len()),
+    }
+    Ok(())
+}
@@ -0,0 +1,459 @@
+use crate::solution::{Direction, Map, Pos, Tile};
+
+/// Marker in the jump tables for the guard walking off the map.
+const EXIT: u32 = u32::MAX;
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+];
+
+impl Direction {
+    /// Index into the jump tables, in clockwise order starting from `Up`.
+    fn index(&self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Right => 1,
+            Direction::Down => 2,
+            Direction::Left => 3,
+        }
+    }
+}
+
+/// A flat bitset over guard states, `cell * 4 + direction`.
+pub struct StateSet {
+    bits: Vec<u64>,
+}
+
+impl StateSet {
+    pub fn new(cells: usize) -> Self {
+        Self {
+            bits: vec![0; (cells * 4).div_ceil(64)],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// Marks the state as seen, returning whether it had already been seen.
+    pub fn insert_seen(&mut self, cell: usize, dir: Direction) -> bool {
+        let state = cell * 4 + dir.index();
+        let (word, bit) = (state / 64, 1 << (state % 64));
+        let seen = self.bits[word] & bit != 0;
+        self.bits[word] |= bit;
+        seen
+    }
+}
+
+/// The route of a single guard without any added obstructions.
+pub struct GuardPath {
+    /// Whether each cell (flat index) is visited by the guard.
+    pub visited: Vec<bool>,
+    /// For every visited cell except the start, the cell and direction the guard had right before first entering it.
+    pub first_entries: Vec<(usize, usize, Direction)>,
+    /// Whether the guard ended up walking in a loop instead of leaving the map.
+    pub looped: bool,
+}
+
+impl GuardPath {
+    pub fn visited_count(&self) -> usize {
+        self.visited.iter().filter(|v| **v).count()
+    }
+}
+
+/// What a guard does during one time step of a patrol.
+enum Move {
+    Step(usize),
+    Turn,
+    /// Turning because another guard is in the way.
+    Blocked,
+    Leave,
+}
+
+/// A guard during a patrol with other guards.
+struct Patroller {
+    cell: usize,
+    dir: Direction,
+    on_map: bool,
+    visited: Vec<bool>,
+    first_entries: Vec<(usize, usize, Direction)>,
+    /// States reached since the guard last had another guard in its way.
+    seen: StateSet,
+    /// The cells of the loop the guard walks, once it has gone round it without meeting another guard.
+    loop_cells: Option<Vec<bool>>,
+}
+
+impl Patroller {
+    fn new(cell: usize, dir: Direction, cells: usize) -> Self {
+        let mut visited = vec![false; cells];
+        visited[cell] = true;
+        let mut seen = StateSet::new(cells);
+        seen.insert_seen(cell, dir);
+
+        Self {
+            cell,
+            dir,
+            on_map: true,
+            visited,
+            first_entries: vec![],
+            seen,
+            loop_cells: None,
+        }
+    }
+
+    fn enter(&mut self, next: usize) {
+        if !self.visited[next] {
+            self.visited[next] = true;
+            self.first_entries.push((next, self.cell, self.dir));
+        }
+        self.cell = next;
+    }
+
+    /// Forgets the loop, as meeting another guard may have changed the route.
+    fn met_guard(&mut self) {
+        self.seen.clear();
+        self.loop_cells = None;
+    }
+
+    fn snapshot(guards: &[Patroller]) -> Vec<(usize, Direction, bool)> {
+        guards.iter().map(|g| (g.cell, g.dir, g.on_map)).collect()
+    }
+}
+
+pub struct Simulator {
+    width: usize,
+    height: usize,
+    obstructions: Vec<bool>,
+    /// For each direction and cell, the last free cell before the next obstruction in that direction,
+    /// or `EXIT` if the guard would walk off the map.
+    jumps: [Vec<u32>; 4],
+}
+
+impl Simulator {
+    pub fn new(map: &Map) -> Self {
+        let width = map.width;
+        let height = map.height;
+        let obstructions: Vec<bool> = map
+            .map
+            .iter()
+            .flat_map(|row| row.iter().map(|t| *t == Tile::Obstruction))
+            .collect();
+
+        let mut sim = Self {
+            width,
+            height,
+            obstructions,
+            jumps: [vec![], vec![], vec![], vec![]],
+        };
+
+        for dir in DIRECTIONS {
+            // Fill cells closest to the edge the guard walks towards first, so the next cell's jump is always known.
+            let cells: Vec<usize> = match dir {
+                Direction::Up | Direction::Left => (0..width * height).collect(),
+                Direction::Down | Direction::Right => (0..width * height).rev().collect(),
+            };
+
+            let mut jumps = vec![EXIT; width * height];
+            for cell in cells {
+                jumps[cell] = match sim.step(cell, dir) {
+                    None => EXIT,
+                    Some(next) if sim.obstructions[next] => cell as u32,
+                    Some(next) => jumps[next],
+                };
+            }
+            sim.jumps[dir.index()] = jumps;
+        }
+
+        sim
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn to_cell(&self, pos: &Pos) -> usize {
+        pos.y * self.width + pos.x
+    }
+
+    pub fn to_pos(&self, cell: usize) -> Pos {
+        Pos {
+            x: cell % self.width,
+            y: cell / self.width,
+        }
+    }
+
+    /// The neighbouring cell in `dir`, if it is on the map.
+    pub fn step(&self, cell: usize, dir: Direction) -> Option<usize> {
+        let (x, y) = (cell % self.width, cell / self.width);
+        match dir {
+            Direction::Up if y > 0 => Some(cell - self.width),
+            Direction::Down if y + 1 < self.height => Some(cell + self.width),
+            Direction::Left if x > 0 => Some(cell - 1),
+            Direction::Right if x + 1 < self.width => Some(cell + 1),
+            _ => None,
+        }
+    }
+
+    /// Walks the guard one cell at a time until it leaves the map or starts repeating itself.
+    pub fn guard_path(&self, start: usize, start_dir: Direction) -> GuardPath {
+        let mut visited = vec![false; self.cell_count()];
+        let mut first_entries = vec![];
+        let mut seen = StateSet::new(self.cell_count());
+        let mut looped = false;
+
+        let mut cell = start;
+        let mut dir = start_dir;
+        visited[cell] = true;
+
+        while let Some(next) = self.step(cell, dir) {
+            if self.obstructions[next] {
+                dir = dir.rotate_90();
+                // Only turns need remembering, a loop always passes through the same turn twice.
+                if seen.insert_seen(cell, dir) {
+                    looped = true;
+                    break;
+                }
+                continue;
+            }
+
+            if !visited[next] {
+                visited[next] = true;
+                first_entries.push((next, cell, dir));
+            }
+            cell = next;
+        }
+
+        GuardPath {
+            visited,
+            first_entries,
+            looped,
+        }
+    }
+
+    /// Walks several guards at the same time, each one moving or turning once per time step, in reading order.
+    /// A guard turns instead of stepping onto a cell held by another guard at the start of the step,
+    /// or one an earlier guard already stepped onto during it, just as it would for an obstruction.
+    /// Stops once every guard has left the map or is stuck in a loop.
+    pub fn patrol(&self, starts: &[(usize, Direction)]) -> Vec<GuardPath> {
+        let mut guards: Vec<Patroller> = starts
+            .iter()
+            .map(|(cell, dir)| Patroller::new(*cell, *dir, self.cell_count()))
+            .collect();
+        let mut held = vec![false; self.cell_count()];
+        for guard in guards.iter() {
+            held[guard.cell] = true;
+        }
+
+        // Brent's cycle detection on the state of all guards, for loops where guards keep meeting each other.
+        let mut saved = Patroller::snapshot(&guards);
+        let (mut power, mut length) = (1, 0);
+
+        loop {
+            let patrolling: Vec<&Patroller> = guards.iter().filter(|g| g.on_map).collect();
+            if patrolling.is_empty() || Self::loops_apart(&patrolling) {
+                break;
+            }
+
+            let mut entered = vec![];
+            let mut moves = vec![];
+            for (i, guard) in guards.iter().enumerate().filter(|(_, g)| g.on_map) {
+                let next = self.step(guard.cell, guard.dir);
+                match next {
+                    Some(next) if self.obstructions[next] => moves.push((i, Move::Turn)),
+                    Some(next) if held[next] || entered.contains(&next) => {
+                        moves.push((i, Move::Blocked))
+                    }
+                    Some(next) => {
+                        entered.push(next);
+                        moves.push((i, Move::Step(next)));
+                    }
+                    None => moves.push((i, Move::Leave)),
+                }
+            }
+
+            for (i, mv) in moves {
+                let guard = &mut guards[i];
+                match mv {
+                    Move::Step(next) => {
+                        held[guard.cell] = false;
+                        guard.enter(next);
+                    }
+                    Move::Leave => {
+                        held[guard.cell] = false;
+                        guard.on_map = false;
+                        continue;
+                    }
+                    Move::Turn => guard.dir = guard.dir.rotate_90(),
+                    Move::Blocked => {
+                        guard.dir = guard.dir.rotate_90();
+                        guard.met_guard();
+                    }
+                }
+
+                if guard.seen.insert_seen(guard.cell, guard.dir) && guard.loop_cells.is_none() {
+                    guard.loop_cells = Some(self.loop_cells(guard.cell, guard.dir));
+                }
+            }
+            for guard in guards.iter().filter(|g| g.on_map) {
+                held[guard.cell] = true;
+            }
+
+            let state = Patroller::snapshot(&guards);
+            if state == saved {
+                break;
+            }
+            length += 1;
+            if length == power {
+                saved = state;
+                power *= 2;
+                length = 0;
+            }
+        }
+
+        guards
+            .into_iter()
+            .map(|guard| GuardPath {
+                looped: guard.on_map,
+                visited: guard.visited,
+                first_entries: guard.first_entries,
+            })
+            .collect()
+    }
+
+    /// Whether every guard is walking a loop on its own, and no two loops share a cell, so they can never meet again.
+    fn loops_apart(guards: &[&Patroller]) -> bool {
+        let Some(loops) = guards
+            .iter()
+            .map(|g| g.loop_cells.as_ref())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        let mut taken = vec![false; loops[0].len()];
+        for cells in loops {
+            for (cell, on_loop) in cells.iter().enumerate() {
+                if *on_loop {
+                    if taken[cell] {
+                        return false;
+                    }
+                    taken[cell] = true;
+                }
+            }
+        }
+        true
+    }
+
+    /// The cells a lone guard walks through when looping from a state on its loop.
+    fn loop_cells(&self, start: usize, start_dir: Direction) -> Vec<bool> {
+        let mut cells = vec![false; self.cell_count()];
+        let (mut cell, mut dir) = (start, start_dir);
+        loop {
+            cells[cell] = true;
+            match self.step(cell, dir) {
+                Some(next) if self.obstructions[next] => dir = dir.rotate_90(),
+                Some(next) => cell = next,
+                None => unreachable!("A guard on a loop never leaves the map"),
+            }
+            if (cell, dir) == (start, start_dir) {
+                return cells;
+            }
+        }
+    }
+
+    /// Walks the guard one cell at a time, optionally with an added obstruction at `extra`,
+    /// recording the state after every move and every turn.
+    /// Returns the route and whether it ended in a loop rather than leaving the map.
+    pub fn route(
+        &self,
+        start: usize,
+        start_dir: Direction,
+        extra: Option<usize>,
+    ) -> (Vec<(usize, Direction)>, bool) {
+        let mut seen = StateSet::new(self.cell_count());
+        let mut route = vec![(start, start_dir)];
+        seen.insert_seen(start, start_dir);
+
+        let mut cell = start;
+        let mut dir = start_dir;
+        while let Some(next) = self.step(cell, dir) {
+            if self.obstructions[next] || Some(next) == extra {
+                dir = dir.rotate_90();
+            } else {
+                cell = next;
+            }
+
+            if seen.insert_seen(cell, dir) {
+                return (route, true);
+            }
+            route.push((cell, dir));
+        }
+
+        (route, false)
+    }
+
+    /// Whether the guard, starting at `start` facing `start_dir`, ends up in a loop if an obstruction is added at `extra`.
+    /// Jumps straight from turn to turn, only remembering the states at each turn.
+    pub fn is_loop_with(
+        &self,
+        start: usize,
+        start_dir: Direction,
+        extra: usize,
+        seen: &mut StateSet,
+    ) -> bool {
+        seen.clear();
+
+        let mut cell = start;
+        let mut dir = start_dir;
+        loop {
+            let mut target = self.jumps[dir.index()][cell];
+            if self.blocks(cell, dir, target, extra) {
+                // Stop on the cell right before the added obstruction.
+                target = self.step(extra, dir.rotate_90().rotate_90()).unwrap() as u32;
+            }
+
+            if target == EXIT {
+                return false;
+            }
+
+            cell = target as usize;
+            if seen.insert_seen(cell, dir) {
+                return true;
+            }
+            dir = dir.rotate_90();
+        }
+    }
+
+    /// Whether `extra` lies ahead of `cell` in `dir` and no further than the obstruction ending the jump to `target`.
+    fn blocks(&self, cell: usize, dir: Direction, target: u32, extra: usize) -> bool {
+        let (cx, cy) = (cell % self.width, cell / self.width);
+        let (ex, ey) = (extra % self.width, extra / self.width);
+        let target = (target != EXIT).then(|| {
+            let target = target as usize;
+            (target % self.width, target / self.width)
+        });
+
+        match dir {
+            Direction::Up => ex == cx && ey < cy && target.is_none_or(|(_, ty)| ey >= ty),
+            Direction::Down => ex == cx && ey > cy && target.is_none_or(|(_, ty)| ey <= ty),
+            Direction::Left => ey == cy && ex < cx && target.is_none_or(|(tx, _)| ex >= tx),
+            Direction::Right => ey == cy && ex > cx && target.is_none_or(|(tx, _)| ex <= tx),
+        }
+    }
+
+    /// Every cell on the guard's original route where an added obstruction traps the guard in a loop.
+    /// Each candidate is simulated from the state right before the guard first reaches it, as the route up to there is unchanged.
+    pub fn loop_obstructions(&self, start: usize, start_dir: Direction) -> Vec<usize> {
+        let path = self.guard_path(start, start_dir);
+        let mut seen = StateSet::new(self.cell_count());
+
+        path.first_entries
+            .iter()
+            .filter(|(candidate, from, dir)| self.is_loop_with(*from, *dir, *candidate, &mut seen))
+            .map(|(candidate, _, _)| *candidate)
+            .collect()
+    }
+}
@@ -1,9 +1,11 @@
-use std::collections::HashSet;
+use std::fmt::Display;
+
+use crate::{patrol::PatrolReport, simulation::Simulator};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Pos {
-    x: usize,
-    y: usize,
+    pub x: usize,
+    pub y: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -14,42 +16,54 @@ pub enum Tile {
 }
 
 pub struct Map {
-    width: usize,
-    height: usize,
-    map: Vec<Vec<Tile>>,
+    pub width: usize,
+    pub height: usize,
+    pub map: Vec<Vec<Tile>>,
 }
 
-impl Map {
-    pub fn translate_in(&self, pos: &Pos, dir: &Direction) -> Option<Pos> {
-        let (delta_x, delta_y) = match dir {
-            Direction::Up => (0, -1),
-            Direction::Down => (0, 1),
-            Direction::Right => (1, 0),
-            Direction::Left => (-1, 0),
-        };
-
-        let updated_x = (pos.x as i32) + delta_x;
-        let updated_y = (pos.y as i32) + delta_y;
-
-        if updated_x < 0 || updated_y < 0 {
-            return None;
-        }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardError {
+    NoGuard,
+    MultipleGuards(usize),
+}
 
-        let updated_x = updated_x as usize;
-        let updated_y = updated_y as usize;
+impl Display for GuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardError::NoGuard => write!(f, "No guard (^, >, v or <) found on the map"),
+            GuardError::MultipleGuards(n) => {
+                write!(f, "Expected a single guard on the map but found {n}")
+            }
+        }
+    }
+}
 
-        if updated_x >= self.width || updated_y >= self.height {
-            return None;
+impl Map {
+    /// The start position and direction of every guard, in reading order.
+    pub fn guards(&self) -> Vec<(Pos, Direction)> {
+        let mut guards = vec![];
+        for (y, row) in self.map.iter().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                if let Tile::GuardStart(d) = c {
+                    guards.push((Pos { x, y }, *d));
+                }
+            }
         }
+        guards
+    }
 
-        return Some(Pos {
-            x: updated_x,
-            y: updated_y,
-        });
+    /// The start of the only guard on the map.
+    pub fn guard_start(&self) -> Result<(Pos, Direction), GuardError> {
+        let mut guards = self.guards();
+        match guards.len() {
+            0 => Err(GuardError::NoGuard),
+            1 => Ok(guards.remove(0)),
+            n => Err(GuardError::MultipleGuards(n)),
+        }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
     Down,
@@ -75,6 +89,9 @@ pub fn parse<'a>(input: &str) -> Map {
             l.chars()
                 .map(|c| match c {
                     '^' => Tile::GuardStart(Direction::Up),
+                    '>' => Tile::GuardStart(Direction::Right),
+                    'v' => Tile::GuardStart(Direction::Down),
+                    '<' => Tile::GuardStart(Direction::Left),
                     '.' => Tile::Empty,
                     '#' => Tile::Obstruction,
                     _ => panic!("Unknown tile {c}"),
@@ -89,117 +106,32 @@ pub fn parse<'a>(input: &str) -> Map {
     Map { map, height, width }
 }
 
-pub fn solve_part_one<'a>(map: Map) -> String {
-    let mut guard_pos = Pos { x: 0, y: 0 };
-    let mut guard_dir = Direction::Down;
-
-    let mut visited: HashSet<Pos> = HashSet::new();
-
-    for (y, row) in map.map.iter().enumerate() {
-        for (x, c) in row.iter().enumerate() {
-            if let Tile::GuardStart(d) = c {
-                guard_pos = Pos { x, y };
-                guard_dir = d.clone();
-            }
-        }
-    }
-
-    visited.insert(guard_pos.clone());
-
-    loop {
-        let Some(new_pos) = map.translate_in(&guard_pos, &guard_dir) else {
-            break;
-        };
-
-        // println!("new_pos {new_pos:?} {guard_dir:?} ({guard_pos:?})");
-
-        match map.map[new_pos.y][new_pos.x] {
-            Tile::Obstruction => {
-                // println!("Obstruction!");
-                guard_dir = guard_dir.rotate_90();
-                continue;
-            }
-            ref c => {
-                // println!("Got tile {c:?}");
-                visited.insert(new_pos.clone());
-                guard_pos = new_pos;
-            }
-        }
-    }
-
-    visited.len().to_string()
+/// Reports a map that can't be solved and exits, rather than handing the error over as an answer.
+pub fn fail(e: impl Display) -> ! {
+    eprintln!("{e}");
+    std::process::exit(1)
 }
 
-fn is_loop(map: &Map, start_pos: Pos, start_dir: Direction) -> bool {
-    let mut curr_pos = start_pos;
-    let mut curr_dir = start_dir;
-
-    let mut visited: HashSet<(Pos, Direction)> = HashSet::new();
-    visited.insert((curr_pos.clone(), curr_dir.clone()));
-
-    loop {
-        let Some(new_pos) = map.translate_in(&curr_pos, &curr_dir) else {
-            return false;
-        };
-
-        if visited.contains(&(new_pos.clone(), curr_dir.clone())) {
-            return true;
-        }
-
-        match map.map[new_pos.y][new_pos.x] {
-            Tile::Obstruction => {
-                curr_dir = curr_dir.rotate_90();
-                continue;
-            }
-            _ => {
-                visited.insert((new_pos.clone(), curr_dir.clone()));
-                curr_pos = new_pos;
-            }
-        }
-    }
+/// With several guards on the map this counts the positions visited by any of them.
+pub fn solve_part_one<'a>(map: Map) -> String {
+    visited_positions(&map)
+        .unwrap_or_else(|e| fail(e))
+        .to_string()
 }
 
-pub fn solve_part_two<'a>(input: Map) -> String {
-    let mut guard_pos = Pos { x: 0, y: 0 };
-    let mut guard_dir = Direction::Down;
+pub fn solve_part_two<'a>(map: Map) -> String {
+    loop_positions(&map).unwrap_or_else(|e| fail(e)).to_string()
+}
 
-    for (y, row) in input.map.iter().enumerate() {
-        for (x, c) in row.iter().enumerate() {
-            if let Tile::GuardStart(d) = c {
-                guard_pos = Pos { x, y };
-                guard_dir = d.clone();
-            }
-        }
-    }
+pub fn visited_positions(map: &Map) -> Result<usize, GuardError> {
+    Ok(PatrolReport::new(map)?.union)
+}
 
-    let mut tiles = input.map.clone();
-    let mut loop_counts = 0;
-
-    for y in 0..input.height {
-        for x in 0..input.width {
-            // println!("Checking ({x}, {y})");
-            match input.map[y][x] {
-                Tile::Empty => {
-                    tiles[y][x] = Tile::Obstruction;
-                    if is_loop(
-                        &Map {
-                            width: input.width,
-                            height: input.height,
-                            map: tiles.clone(),
-                        },
-                        guard_pos.clone(),
-                        guard_dir.clone(),
-                    ) {
-                        loop_counts += 1;
-                    }
-                    tiles[y][x] = Tile::Empty;
-                }
-                _ => {
-                    continue;
-                }
-            }
-        }
-    }
+pub fn loop_positions(map: &Map) -> Result<usize, GuardError> {
+    let (guard_pos, guard_dir) = map.guard_start()?;
+    let sim = Simulator::new(map);
 
-    loop_counts.to_string()
+    Ok(sim
+        .loop_obstructions(sim.to_cell(&guard_pos), guard_dir)
+        .len())
 }
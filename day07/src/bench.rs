@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+use crate::{forward, solution};
+
+const RUNS: u32 = 10;
+
+/// A named part with its reverse and forward solvers.
+type Part = (&'static str, fn(&str) -> String, fn(&str) -> String);
+
+fn time(f: impl Fn() -> String) -> (String, Duration) {
+    let start = Instant::now();
+    let mut res = String::new();
+    for _ in 0..RUNS {
+        res = f();
+    }
+    (res, start.elapsed() / RUNS)
+}
+
+/// Times the reverse search against the original forward search on the same input,
+/// checking that both give the same answers.
+pub fn compare(input: &str) {
+    let parts: [Part; 2] = [
+        (
+            "Part one",
+            |i| solution::solve_part_one(solution::parse(i)),
+            |i| forward::solve_part_one(solution::parse(i)),
+        ),
+        (
+            "Part two",
+            |i| solution::solve_part_two(solution::parse(i)),
+            |i| forward::solve_part_two(solution::parse(i)),
+        ),
+    ];
+
+    for (name, reverse, forward) in parts {
+        let (reverse_res, reverse_time) = time(|| reverse(input));
+        let (forward_res, forward_time) = time(|| forward(input));
+
+        println!("{name}: reverse {reverse_res} in {reverse_time:?}, forward {forward_res} in {forward_time:?}");
+        assert_eq!(reverse_res, forward_res, "{name} answers differ");
+    }
+}
@@ -0,0 +1,85 @@
+//! The original forward search, trying every operator from the left.
+//! Kept around to benchmark the reverse search in `solution` against.
+
+fn get_res_rec(sought: u64, curr: u64, index: usize, vals: &[u64]) -> bool {
+    if curr > sought {
+        return false;
+    }
+
+    if index == vals.len() {
+        return sought == curr;
+    }
+
+    let v = vals[index];
+
+    // Try addition first
+    let sum = curr + v;
+    if get_res_rec(sought, sum, index + 1, vals) {
+        return true;
+    }
+
+    let product = curr * v;
+    if get_res_rec(sought, product, index + 1, vals) {
+        return true;
+    }
+
+    false
+}
+
+fn can_get_res(res: u64, vals: Vec<u64>) -> bool {
+    let start_val = vals[0];
+    get_res_rec(res, start_val, 1, &vals)
+}
+
+pub fn solve_part_one(input: impl Iterator<Item = (u64, Vec<u64>)>) -> String {
+    input
+        .filter(|(res, vals)| can_get_res(*res, vals.clone()))
+        .map(|(res, _)| res)
+        .sum::<u64>()
+        .to_string()
+}
+
+fn get_res_rec_2(sought: u64, curr: u64, index: usize, vals: &[u64]) -> bool {
+    if curr > sought {
+        return false;
+    }
+
+    if index == vals.len() {
+        return sought == curr;
+    }
+
+    let v = vals[index];
+    let n_index = index + 1;
+
+    // Try addition first
+    let sum = curr + v;
+    if get_res_rec_2(sought, sum, n_index, vals) {
+        return true;
+    }
+
+    let product = curr * v;
+    if get_res_rec_2(sought, product, n_index, vals) {
+        return true;
+    }
+
+    let combined_str = format!("{curr}{v}");
+    let combined = combined_str.parse::<u64>().unwrap();
+    if get_res_rec_2(sought, combined, n_index, vals) {
+        return true;
+    }
+
+    false
+}
+
+fn can_get_res_2(res: u64, vals: Vec<u64>) -> bool {
+    let start_val = vals[0];
+    get_res_rec_2(res, start_val, 1, &vals)
+}
+
+pub fn solve_part_two(input: impl Iterator<Item = (u64, Vec<u64>)>) -> String {
+    input
+        .filter(|(res, vals)| can_get_res_2(*res, vals.clone()))
+        .map(|(res, _)| res)
+        .sum::<u64>()
+        .to_string()
+}
@@ -2,11 +2,36 @@
 
 use generate_aoc_day::generate_day;
 
+mod bench;
+mod forward;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 7);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("bench") => bench::compare(&mode_input()),
+        Ok("equations") => {
+            let ops: Vec<solution::Operator> = match std::env::var("operators") {
+                Ok(ops) => ops
+                    .split(',')
+                    .map(|op| op.trim().parse().unwrap_or_else(|e| panic!("{e}")))
+                    .collect(),
+                Err(_) if std::env::var("part").as_deref() == Ok("part1") => {
+                    solution::PART_ONE_OPERATORS.to_vec()
+                }
+                Err(_) => solution::PART_TWO_OPERATORS.to_vec(),
+            };
+            let all = std::env::var("all").is_ok();
+            solution::print_equations(&mode_input(), &ops, all);
+        }
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
@@ -1,3 +1,5 @@
+use std::{fmt::Display, ops::ControlFlow, str::FromStr};
+
 pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = (u64, Vec<u64>)> + 'a {
     input.lines().map(|l| {
         let (res, vals) = l.split_once(": ").unwrap();
@@ -10,87 +12,276 @@ pub fn parse<'a>(input: &'a str) -> impl Iterator<Item = (u64, Vec<u64>)> + 'a {
     })
 }
 
-fn get_res_rec(sought: u64, curr: u64, index: usize, vals: &Vec<u64>) -> bool {
-    if sought > curr {
-        return false;
-    }
+pub const PART_ONE_OPERATORS: [Operator; 2] = [Operator::Add, Operator::Multiply];
+pub const PART_TWO_OPERATORS: [Operator; 3] =
+    [Operator::Add, Operator::Multiply, Operator::Concatenate];
+
+/// Operators are always evaluated left-to-right and intermediate values may not go negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Concatenate,
+    Power,
+}
+
+impl FromStr for Operator {
+    type Err = String;
 
-    if vals.is_empty() {
-        return sought == curr;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Operator::Add),
+            "-" => Ok(Operator::Subtract),
+            "*" => Ok(Operator::Multiply),
+            "||" => Ok(Operator::Concatenate),
+            "^" => Ok(Operator::Power),
+            _ => Err(format!("Unknown operator '{s}'")),
+        }
     }
+}
 
-    let v = vals[index];
+/// The result of undoing an operator.
+enum Undo {
+    /// No value before the operator gives the target.
+    Impossible,
+    /// Exactly this value before the operator gives the target.
+    Value(u128),
+    /// Any value before the operator gives the target, e.g. multiplying by zero.
+    Any,
+}
 
-    // Try addition first
-    let sum = curr + v;
-    if get_res_rec(sought, sum, index + 1, vals) {
-        return true;
+impl Operator {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Concatenate => "||",
+            Operator::Power => "^",
+        }
     }
 
-    let product = curr * v;
-    if get_res_rec(sought, product, index + 1, vals) {
-        return true;
+    /// `prev op last`, or `None` if that overflows or goes negative.
+    fn apply(&self, prev: u128, last: u128) -> Option<u128> {
+        match self {
+            Operator::Add => prev.checked_add(last),
+            Operator::Subtract => prev.checked_sub(last),
+            Operator::Multiply => prev.checked_mul(last),
+            Operator::Concatenate => prev
+                .checked_mul(10u128.checked_pow(digits(last))?)?
+                .checked_add(last),
+            Operator::Power => prev.checked_pow(u32::try_from(last).ok()?),
+        }
     }
 
-    return false;
+    /// Finds what the expression before `last` must evaluate to for `prev op last` to equal `target`.
+    fn undo(&self, target: u128, last: u128) -> Undo {
+        let value = |v: Option<u128>| v.map_or(Undo::Impossible, Undo::Value);
+        match self {
+            Operator::Add => value(target.checked_sub(last)),
+            Operator::Subtract => value(target.checked_add(last)),
+            Operator::Multiply if last == 0 && target == 0 => Undo::Any,
+            Operator::Multiply if last == 0 || !target.is_multiple_of(last) => Undo::Impossible,
+            Operator::Multiply => Undo::Value(target / last),
+            Operator::Concatenate => value(
+                10u128
+                    .checked_pow(digits(last))
+                    .filter(|shift| target % shift == last)
+                    .map(|shift| target / shift),
+            ),
+            Operator::Power if last == 0 && target == 1 => Undo::Any,
+            Operator::Power if last == 0 => Undo::Impossible,
+            Operator::Power => value(
+                u32::try_from(last)
+                    .ok()
+                    .and_then(|exp| exact_root(target, exp)),
+            ),
+        }
+    }
 }
 
-fn can_get_res(res: u64, vals: Vec<u64>) -> bool {
-    let start_val = vals[0];
-    get_res_rec(res, start_val, 1, &vals)
+fn digits(mut n: u128) -> u32 {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
 }
 
-pub fn solve_part_one<'a>(input: impl Iterator<Item = (u64, Vec<u64>)>) -> String {
-    input
-        .filter(|(res, vals)| can_get_res(*res, vals.clone()))
-        .map(|(res, vals)| (res, vals))
-        .map(|(res, _)| res)
-        .sum::<u64>()
-        .to_string()
+/// The integer `root` such that `root^exp == target`, if there is one.
+fn exact_root(target: u128, exp: u32) -> Option<u128> {
+    // The root is never larger than the target, and the inclusive upper bound can't overflow.
+    let (mut low, mut high) = (0u128, target);
+    while low <= high {
+        let mid = low + (high - low) / 2;
+        match mid.checked_pow(exp) {
+            Some(p) if p == target => return Some(mid),
+            Some(p) if p < target => low = mid + 1,
+            _ => high = mid.checked_sub(1)?,
+        }
+    }
+    None
 }
 
-fn get_res_rec_2(sought: u64, curr: u64, index: usize, vals: &Vec<u64>) -> bool {
-    if curr > sought {
-        return false;
-    }
+/// A calibration equation with the operators placed between its values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub target: u64,
+    pub values: Vec<u64>,
+    pub operators: Vec<Operator>,
+}
 
-    if index == vals.len() {
-        return sought == curr;
+impl Display for Equation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.target, self.values[0])?;
+        for (op, v) in self.operators.iter().zip(self.values[1..].iter()) {
+            write!(f, " {} {v}", op.symbol())?;
+        }
+        Ok(())
     }
+}
 
-    let v = vals[index];
-    let n_index = index + 1;
+/// Searches backwards from the target, undoing the last operator at each step.
+/// Only non-negative subtraction, exact division, matching decimal suffixes and so on are possible,
+/// which prunes nearly every branch early.
+/// `undone` holds the operators undone so far, last operator first.
+fn search_reverse(
+    target: u128,
+    vals: &[u64],
+    ops: &[Operator],
+    undone: &mut Vec<Operator>,
+    on_found: &mut impl FnMut(Vec<Operator>) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let Some((last, rest)) = vals.split_last() else {
+        return ControlFlow::Continue(());
+    };
+    let last = *last as u128;
 
-    // Try addition first
-    let sum = curr + v;
-    if get_res_rec_2(sought, sum, n_index, vals) {
-        return true;
+    if rest.is_empty() {
+        if target == last {
+            return on_found(undone.iter().rev().copied().collect());
+        }
+        return ControlFlow::Continue(());
     }
 
-    let product = curr * v;
-    if get_res_rec_2(sought, product, n_index, vals) {
-        return true;
+    for op in ops.iter() {
+        undone.push(*op);
+        let flow = match op.undo(target, last) {
+            Undo::Impossible => ControlFlow::Continue(()),
+            Undo::Value(prev) => search_reverse(prev, rest, ops, undone, on_found),
+            Undo::Any => {
+                search_forward(rest[0] as u128, &rest[1..], ops, &mut vec![], &mut |ops| {
+                    on_found(
+                        ops.into_iter()
+                            .chain(undone.iter().rev().copied())
+                            .collect(),
+                    )
+                })
+            }
+        };
+        undone.pop();
+        flow?;
     }
 
-    let combined_str = format!("{curr}{v}");
-    let combined = combined_str.parse::<u64>().unwrap();
-    if get_res_rec_2(sought, combined, n_index, vals) {
-        return true;
+    ControlFlow::Continue(())
+}
+
+/// Tries every operator sequence from the left, reporting each one that evaluates without overflowing or going negative.
+fn search_forward(
+    curr: u128,
+    vals: &[u64],
+    ops: &[Operator],
+    applied: &mut Vec<Operator>,
+    on_found: &mut impl FnMut(Vec<Operator>) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let Some((next, rest)) = vals.split_first() else {
+        return on_found(applied.clone());
+    };
+
+    for op in ops.iter() {
+        if let Some(res) = op.apply(curr, *next as u128) {
+            applied.push(*op);
+            let flow = search_forward(res, rest, ops, applied, on_found);
+            applied.pop();
+            flow?;
+        }
     }
 
-    return false;
+    ControlFlow::Continue(())
 }
 
-fn can_get_res_2(res: u64, vals: Vec<u64>) -> bool {
-    let start_val = vals[0];
-    get_res_rec_2(res, start_val, 1, &vals)
+/// The first operator sequence found that makes the values equal the target.
+pub fn find_equation(target: u64, vals: &[u64], ops: &[Operator]) -> Option<Equation> {
+    let mut found = None;
+    let _ = search_reverse(target as u128, vals, ops, &mut vec![], &mut |operators| {
+        found = Some(operators);
+        ControlFlow::Break(())
+    });
+
+    found.map(|operators| Equation {
+        target,
+        values: vals.to_vec(),
+        operators,
+    })
 }
 
-pub fn solve_part_two<'a>(input: impl Iterator<Item = (u64, Vec<u64>)>) -> String {
+/// Every operator sequence that makes the values equal the target.
+pub fn find_all_equations(target: u64, vals: &[u64], ops: &[Operator]) -> Vec<Equation> {
+    let mut found = vec![];
+    let _ = search_reverse(target as u128, vals, ops, &mut vec![], &mut |operators| {
+        found.push(Equation {
+            target,
+            values: vals.to_vec(),
+            operators,
+        });
+        ControlFlow::Continue(())
+    });
+    found
+}
+
+pub fn calibration_result(
+    input: impl Iterator<Item = (u64, Vec<u64>)>,
+    ops: &[Operator],
+) -> String {
     input
-        .filter(|(res, vals)| can_get_res_2(*res, vals.clone()))
-        .map(|(res, vals)| (res, vals))
-        .map(|(res, _)| res)
-        .sum::<u64>()
+        .filter(|(res, vals)| find_equation(*res, vals, ops).is_some())
+        .map(|(res, _)| res as u128)
+        .sum::<u128>()
         .to_string()
 }
+
+pub fn solve_part_one<'a>(input: impl Iterator<Item = (u64, Vec<u64>)>) -> String {
+    calibration_result(input, &PART_ONE_OPERATORS)
+}
+
+pub fn solve_part_two<'a>(input: impl Iterator<Item = (u64, Vec<u64>)>) -> String {
+    calibration_result(input, &PART_TWO_OPERATORS)
+}
+
+/// Prints the equation (or every equation when `all` is set) found for each calibration line.
+pub fn print_equations(input: &str, ops: &[Operator], all: bool) {
+    let mut total = 0u128;
+    for (target, vals) in parse(input) {
+        let equations = if all {
+            find_all_equations(target, &vals, ops)
+        } else {
+            find_equation(target, &vals, ops).into_iter().collect()
+        };
+
+        if equations.is_empty() {
+            println!("{target}: no solution");
+            continue;
+        }
+
+        total += target as u128;
+        for equation in equations.iter() {
+            println!("{equation}");
+        }
+        if all {
+            println!("  {} solution(s)", equations.len());
+        }
+    }
+    println!("Total calibration result: {total}");
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+pub struct AntennaMap {
+    pub width: usize,
+    pub height: usize,
+    /// Antenna positions grouped by frequency.
+    pub antennas: BTreeMap<char, Vec<(usize, usize)>>,
+}
+
+/// A ratio `p:q` between an antinode's distances to two antennas of the same frequency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ratio {
+    pub p: u32,
+    pub q: u32,
+}
+
+/// Where antinodes appear along the line through a pair of antennas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Harmonics {
+    /// Beyond each antenna, where one antenna is twice as far away as the other.
+    Exact,
+    /// Every multiple of the distance between the antennas along the line, including the antennas themselves.
+    AllMultiples,
+    /// Wherever the distances to the two antennas are in one of the given ratios, either way around.
+    /// That is one point between the antennas and, unless the ratio is `1:1`, one beyond the nearer antenna.
+    Custom(Vec<Ratio>),
+}
+
+impl FromStr for Harmonics {
+    type Err = String;
+
+    /// Accepts `exact`, `all` or a comma separated list of `p:q` ratios.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(Harmonics::Exact),
+            "all" => Ok(Harmonics::AllMultiples),
+            ratios => ratios
+                .split(',')
+                .map(|ratio| {
+                    let (p, q) = ratio
+                        .split_once(':')
+                        .ok_or(format!("Invalid ratio '{ratio}', expected 'p:q'"))?;
+                    let number = |n: &str| {
+                        n.trim()
+                            .parse::<u32>()
+                            .map_err(|_| format!("Invalid number '{n}' in ratio '{ratio}'"))
+                    };
+                    let (p, q) = (number(p)?, number(q)?);
+                    if p == 0 && q == 0 {
+                        return Err(String::from("Ratio 0:0 is not allowed"));
+                    }
+                    Ok(Ratio { p, q })
+                })
+                .collect::<Result<Vec<Ratio>, String>>()
+                .map(Harmonics::Custom),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Antinodes {
+    pub per_frequency: BTreeMap<char, BTreeSet<(usize, usize)>>,
+}
+
+impl Antinodes {
+    /// Number of distinct locations containing an antinode of any frequency.
+    pub fn unique_count(&self) -> usize {
+        self.per_frequency
+            .values()
+            .flatten()
+            .collect::<BTreeSet<&(usize, usize)>>()
+            .len()
+    }
+}
+
+impl AntennaMap {
+    fn in_bounds(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return None;
+        }
+        Some((x as usize, y as usize))
+    }
+
+    /// The antinodes of one antenna pair taken from `from` towards `to`.
+    /// The puzzle's harmonics lie beyond `to`, custom ratios can also put them between the two antennas.
+    fn pair_antinodes(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        harmonics: &Harmonics,
+    ) -> Vec<(usize, usize)> {
+        let (dx, dy) = (to.0 as i64 - from.0 as i64, to.1 as i64 - from.1 as i64);
+        let at =
+            |n: usize| self.in_bounds(to.0 as i64 + dx * n as i64, to.1 as i64 + dy * n as i64);
+
+        match harmonics {
+            Harmonics::Exact => at(1).into_iter().collect(),
+            Harmonics::AllMultiples => (0..).map_while(at).collect(),
+            Harmonics::Custom(ratios) => ratios
+                .iter()
+                .flat_map(|ratio| self.ratio_points(to, from, *ratio))
+                .collect(),
+        }
+    }
+
+    /// The grid cells on the line through `a` and `b` whose distances to `a` and `b` are in the ratio `p:q`.
+    /// Writing such a point as `a + t * (b - a)`, it's either between them at `t = p / (p + q)`
+    /// or outside at `t = p / (p - q)`.
+    fn ratio_points(
+        &self,
+        a: (usize, usize),
+        b: (usize, usize),
+        ratio: Ratio,
+    ) -> Vec<(usize, usize)> {
+        let (dx, dy) = (b.0 as i64 - a.0 as i64, b.1 as i64 - a.1 as i64);
+        let (p, q) = (ratio.p as i64, ratio.q as i64);
+
+        let mut fractions = vec![(p, p + q)];
+        if p != q {
+            fractions.push((p, p - q));
+        }
+
+        fractions
+            .into_iter()
+            .filter(|(num, den)| (dx * num) % den == 0 && (dy * num) % den == 0)
+            .filter_map(|(num, den)| {
+                self.in_bounds(a.0 as i64 + dx * num / den, a.1 as i64 + dy * num / den)
+            })
+            .collect()
+    }
+
+    /// Computes antinodes for every pair of antennas sharing a frequency.
+    /// When `frequencies` is given only those frequencies are considered.
+    pub fn antinodes(&self, harmonics: &Harmonics, frequencies: Option<&[char]>) -> Antinodes {
+        let mut antinodes = Antinodes::default();
+
+        for (freq, positions) in self.antennas.iter() {
+            if frequencies.is_some_and(|fs| !fs.contains(freq)) {
+                continue;
+            }
+
+            let locations = antinodes.per_frequency.entry(*freq).or_default();
+            for (i, a) in positions.iter().enumerate() {
+                for b in positions[i + 1..].iter() {
+                    locations.extend(self.pair_antinodes(*a, *b, harmonics));
+                    locations.extend(self.pair_antinodes(*b, *a, harmonics));
+                }
+            }
+        }
+
+        antinodes
+    }
+}
+
+pub fn print_antinodes(map: &AntennaMap, harmonics: &Harmonics, frequencies: Option<&[char]>) {
+    let antinodes = map.antinodes(harmonics, frequencies);
+    for (freq, locations) in antinodes.per_frequency.iter() {
+        let locations = locations
+            .iter()
+            .map(|(x, y)| format!("({x}, {y})"))
+            .collect::<Vec<String>>()
+            .join(" ");
+        println!("{freq}: {locations}");
+    }
+    println!("{} unique locations", antinodes.unique_count());
+}
@@ -2,11 +2,37 @@
 
 use generate_aoc_day::generate_day;
 
+mod antinodes;
+mod render;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 8);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok(mode @ ("antinodes" | "render")) => {
+            let harmonics = std::env::var("harmonics")
+                .unwrap_or_else(|_| String::from("exact"))
+                .parse()
+                .unwrap_or_else(|e| panic!("Invalid harmonics: {e}"));
+            let frequencies: Option<Vec<char>> = std::env::var("frequencies")
+                .ok()
+                .map(|f| f.chars().collect());
+            let map = solution::parse(&mode_input());
+
+            if mode == "render" {
+                render::print_render(&map, &harmonics, frequencies.as_deref());
+            } else {
+                antinodes::print_antinodes(&map, &harmonics, frequencies.as_deref());
+            }
+        }
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
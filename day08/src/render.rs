@@ -0,0 +1,58 @@
+use std::collections::BTreeSet;
+
+use crate::antinodes::{AntennaMap, Antinodes, Harmonics};
+
+/// Draws the map with antinodes as `#`, antennas are kept visible where they coincide with an antinode.
+pub fn render_map(map: &AntennaMap, antinodes: &Antinodes) -> String {
+    let mut grid = vec![vec!['.'; map.width]; map.height];
+    for (x, y) in antinodes.per_frequency.values().flatten() {
+        grid[*y][*x] = '#';
+    }
+    for (freq, positions) in map.antennas.iter() {
+        for (x, y) in positions.iter() {
+            grid[*y][*x] = *freq;
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| row.into_iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// A row per frequency with its number of antennas, antinodes,
+/// and how many of those antinodes are also antinodes of some other frequency.
+pub fn render_statistics(map: &AntennaMap, antinodes: &Antinodes) -> String {
+    let mut out = format!(
+        "{:<10}  {:>8}  {:>9}  {:>7}\n",
+        "frequency", "antennas", "antinodes", "overlap"
+    );
+
+    for (freq, locations) in antinodes.per_frequency.iter() {
+        let others: BTreeSet<&(usize, usize)> = antinodes
+            .per_frequency
+            .iter()
+            .filter(|(f, _)| *f != freq)
+            .flat_map(|(_, l)| l.iter())
+            .collect();
+
+        out.push_str(&format!(
+            "{:<10}  {:>8}  {:>9}  {:>7}\n",
+            freq,
+            map.antennas[freq].len(),
+            locations.len(),
+            locations.iter().filter(|l| others.contains(l)).count()
+        ));
+    }
+
+    out.push_str(&format!(
+        "\n{} unique antinode locations\n",
+        antinodes.unique_count()
+    ));
+    out
+}
+
+pub fn print_render(map: &AntennaMap, harmonics: &Harmonics, frequencies: Option<&[char]>) {
+    let antinodes = map.antinodes(harmonics, frequencies);
+    println!("{}", render_map(map, &antinodes));
+    print!("{}", render_statistics(map, &antinodes));
+}
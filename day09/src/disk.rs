@@ -0,0 +1,219 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// Which free extent a whole file is moved into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    /// The leftmost extent that fits.
+    First,
+    /// The smallest extent that fits, the leftmost of those on ties.
+    Best,
+    /// The largest extent, the leftmost of those on ties.
+    Worst,
+}
+
+/// A contiguous run of blocks belonging to a single file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub id: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+/// A contiguous run of free blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    pub start: usize,
+    pub len: usize,
+}
+
+/// A disk modelled as file extents, never expanded into individual blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disk {
+    /// File extents ordered by start, a file may be split over several extents.
+    pub files: Vec<Span>,
+    /// Total number of blocks on the disk.
+    pub size: usize,
+}
+
+impl Disk {
+    /// Reads the dense disk map format, alternating file and free space lengths.
+    pub fn from_dense(map: impl Iterator<Item = u32>) -> Self {
+        let mut files = vec![];
+        let mut pos = 0;
+        for (i, len) in map.enumerate() {
+            let len = len as usize;
+            if i % 2 == 0 && len > 0 {
+                files.push(Span {
+                    id: i / 2,
+                    start: pos,
+                    len,
+                });
+            }
+            pos += len;
+        }
+
+        Self { files, size: pos }
+    }
+
+    /// The free extents between (and after) files, ordered by start.
+    pub fn gaps(&self) -> Vec<Gap> {
+        let mut gaps = vec![];
+        let mut pos = 0;
+        for file in self.files.iter() {
+            if file.start > pos {
+                gaps.push(Gap {
+                    start: pos,
+                    len: file.start - pos,
+                });
+            }
+            pos = file.start + file.len;
+        }
+        if self.size > pos {
+            gaps.push(Gap {
+                start: pos,
+                len: self.size - pos,
+            });
+        }
+        gaps
+    }
+
+    /// Sum of position times file id over every file block, computed per extent.
+    pub fn checksum(&self) -> u64 {
+        self.files
+            .iter()
+            .map(|f| {
+                let (start, len) = (f.start as u64, f.len as u64);
+                // start + (start + 1) + ... + (start + len - 1)
+                let positions = len * start + len * (len - 1) / 2;
+                positions * f.id as u64
+            })
+            .sum()
+    }
+
+    /// A disk of the same size with the given files, sorted and with touching extents of the same file joined.
+    fn with_files(&self, mut files: Vec<Span>) -> Self {
+        files.sort_by_key(|f| f.start);
+        files.dedup_by(|next, prev| {
+            let touching = prev.id == next.id && prev.start + prev.len == next.start;
+            if touching {
+                prev.len += next.len;
+            }
+            touching
+        });
+
+        Self {
+            files,
+            size: self.size,
+        }
+    }
+
+    /// Moves blocks one at a time from the end of the disk into the leftmost free block,
+    /// splitting files as needed, until there are no gaps between files.
+    pub fn compact_blocks(&self) -> Self {
+        let mut files = self.files.clone();
+        let mut moved = vec![];
+
+        for gap in self.gaps() {
+            let mut filled = 0;
+            while filled < gap.len {
+                let Some(last) = files.last_mut() else {
+                    break;
+                };
+                if last.start < gap.start {
+                    break;
+                }
+
+                // Take blocks from the end of the last file.
+                let take = (gap.len - filled).min(last.len);
+                moved.push(Span {
+                    id: last.id,
+                    start: gap.start + filled,
+                    len: take,
+                });
+                filled += take;
+                last.len -= take;
+                if last.len == 0 {
+                    files.pop();
+                }
+            }
+        }
+
+        files.extend(moved);
+        self.with_files(files)
+    }
+
+    /// Attempts to move each whole file once, in order of decreasing file id, into a free extent to its left that fits it.
+    /// Gaps are indexed by a min-heap of starts per gap length, so each file only inspects one heap per length.
+    pub fn compact_files(&self, fit: Fit) -> Self {
+        let gaps = self.gaps();
+        let max_len = gaps.iter().map(|g| g.len).max().unwrap_or(0);
+        let mut free: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); max_len + 1];
+        for gap in gaps.iter() {
+            free[gap.len].push(Reverse(gap.start));
+        }
+
+        let mut files = self.files.clone();
+        files.sort_by_key(|f| Reverse(f.id));
+
+        for file in files.iter_mut() {
+            let leftmost = |len: usize| {
+                free[len]
+                    .peek()
+                    .map(|Reverse(start)| (len, *start))
+                    .filter(|(_, start)| *start < file.start)
+            };
+
+            let found = match fit {
+                Fit::First => (file.len..=max_len)
+                    .filter_map(leftmost)
+                    .min_by_key(|(_, start)| *start),
+                Fit::Best => (file.len..=max_len).find_map(leftmost),
+                Fit::Worst => (file.len..=max_len).rev().find_map(leftmost),
+            };
+            let Some((gap_len, gap_start)) = found else {
+                continue;
+            };
+
+            free[gap_len].pop();
+            if gap_len > file.len {
+                free[gap_len - file.len].push(Reverse(gap_start + file.len));
+            }
+            // The space freed up behind the file is never used, as every remaining file lies before it.
+            file.start = gap_start;
+        }
+
+        self.with_files(files)
+    }
+
+    /// Packs every file contiguously from the start of the disk, keeping their current order
+    /// and joining the extents of split files.
+    pub fn defragment(&self) -> Self {
+        let mut order: Vec<usize> = vec![];
+        let mut lens: HashMap<usize, usize> = HashMap::new();
+        for file in self.files.iter() {
+            if !lens.contains_key(&file.id) {
+                order.push(file.id);
+            }
+            *lens.entry(file.id).or_default() += file.len;
+        }
+
+        let mut pos = 0;
+        let files = order
+            .into_iter()
+            .map(|id| {
+                let span = Span {
+                    id,
+                    start: pos,
+                    len: lens[&id],
+                };
+                pos += span.len;
+                span
+            })
+            .collect();
+
+        self.with_files(files)
+    }
+}
@@ -0,0 +1,171 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::disk::{Disk, Fit};
+
+/// Disks larger than this many blocks are rendered in run notation.
+const MAX_BLOCK_RENDER: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Part one, moving single blocks from the end into the leftmost free block.
+    Blocks,
+    /// Part two, moving whole files into the leftmost free extent that fits.
+    FirstFit,
+    BestFit,
+    WorstFit,
+    /// Packing every file contiguously with no gaps in between.
+    Defragment,
+}
+
+pub const STRATEGIES: [Strategy; 5] = [
+    Strategy::Blocks,
+    Strategy::FirstFit,
+    Strategy::BestFit,
+    Strategy::WorstFit,
+    Strategy::Defragment,
+];
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(Strategy::Blocks),
+            "first-fit" => Ok(Strategy::FirstFit),
+            "best-fit" => Ok(Strategy::BestFit),
+            "worst-fit" => Ok(Strategy::WorstFit),
+            "defragment" => Ok(Strategy::Defragment),
+            _ => Err(format!("Unknown strategy '{s}'")),
+        }
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Strategy::Blocks => "blocks",
+            Strategy::FirstFit => "first-fit",
+            Strategy::BestFit => "best-fit",
+            Strategy::WorstFit => "worst-fit",
+            Strategy::Defragment => "defragment",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Strategy {
+    pub fn apply(&self, disk: &Disk) -> Disk {
+        match self {
+            Strategy::Blocks => disk.compact_blocks(),
+            Strategy::FirstFit => disk.compact_files(Fit::First),
+            Strategy::BestFit => disk.compact_files(Fit::Best),
+            Strategy::WorstFit => disk.compact_files(Fit::Worst),
+            Strategy::Defragment => disk.defragment(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fragmentation {
+    /// Free extents lying between files.
+    pub gaps: usize,
+    /// Free blocks lying between files.
+    pub gap_blocks: usize,
+    /// The largest free extent, including the free space after the last file.
+    pub largest_free: usize,
+    /// Files stored in more than one extent.
+    pub files_split: usize,
+}
+
+impl Display for Fragmentation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} gaps ({} blocks) between files, largest free extent {}, {} files split",
+            self.gaps, self.gap_blocks, self.largest_free, self.files_split
+        )
+    }
+}
+
+pub fn fragmentation(disk: &Disk) -> Fragmentation {
+    let end = disk.files.last().map_or(0, |f| f.start + f.len);
+    let gaps = disk.gaps();
+    let between: Vec<_> = gaps.iter().filter(|g| g.start < end).collect();
+
+    let mut extents: HashMap<usize, usize> = HashMap::new();
+    for file in disk.files.iter() {
+        *extents.entry(file.id).or_default() += 1;
+    }
+
+    Fragmentation {
+        gaps: between.len(),
+        gap_blocks: between.iter().map(|g| g.len).sum(),
+        largest_free: gaps.iter().map(|g| g.len).max().unwrap_or(0),
+        files_split: extents.values().filter(|n| **n > 1).count(),
+    }
+}
+
+/// Renders the disk like the puzzle text (`00...111`) when it is small and every id is a single digit,
+/// otherwise in run notation where `12x3` is three blocks of file 12 and `.x4` four free blocks.
+pub fn render(disk: &Disk) -> String {
+    let mut runs: Vec<(Option<usize>, usize)> = vec![];
+    let mut pos = 0;
+    for file in disk.files.iter() {
+        if file.start > pos {
+            runs.push((None, file.start - pos));
+        }
+        runs.push((Some(file.id), file.len));
+        pos = file.start + file.len;
+    }
+    if disk.size > pos {
+        runs.push((None, disk.size - pos));
+    }
+
+    let symbol = |id: Option<usize>| id.map_or(String::from("."), |id| id.to_string());
+    if disk.size <= MAX_BLOCK_RENDER && disk.files.iter().all(|f| f.id < 10) {
+        runs.into_iter()
+            .map(|(id, len)| symbol(id).repeat(len))
+            .collect()
+    } else {
+        runs.into_iter()
+            .map(|(id, len)| format!("{}x{len}", symbol(id)))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+/// Prints the disk before and after applying `strategy`,
+/// or compares every strategy when none is given.
+pub fn print_layout(disk: &Disk, strategy: Option<Strategy>) {
+    println!("{}", render(disk));
+    println!("{}\n", fragmentation(disk));
+
+    match strategy {
+        Some(strategy) => {
+            let compacted = strategy.apply(disk);
+            println!("After {strategy}:");
+            println!("{}", render(&compacted));
+            println!("{}", fragmentation(&compacted));
+            println!("Checksum {}", compacted.checksum());
+        }
+        None => {
+            println!(
+                "{:<12}  {:>6}  {:>10}  {:>12}  {:>11}  {:>16}",
+                "strategy", "gaps", "gap blocks", "largest free", "files split", "checksum"
+            );
+            for strategy in STRATEGIES {
+                let compacted = strategy.apply(disk);
+                let frag = fragmentation(&compacted);
+                println!(
+                    "{:<12}  {:>6}  {:>10}  {:>12}  {:>11}  {:>16}",
+                    strategy.to_string(),
+                    frag.gaps,
+                    frag.gap_blocks,
+                    frag.largest_free,
+                    frag.files_split,
+                    compacted.checksum()
+                );
+            }
+        }
+    }
+}
@@ -2,11 +2,28 @@
 
 use generate_aoc_day::generate_day;
 
+mod disk;
+mod layout;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 9);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("layout") => {
+            let strategy = std::env::var("strategy")
+                .ok()
+                .map(|s| s.parse().unwrap_or_else(|e| panic!("{e}")));
+            let input = mode_input();
+            layout::print_layout(&disk::Disk::from_dense(solution::parse(&input)), strategy);
+        }
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
@@ -2,11 +2,45 @@
 
 use generate_aoc_day::generate_day;
 
+mod queries;
 mod solution;
+mod trails;
 
 // Replace with desired year/day
 generate_day!(2024, 10);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok("trails") => {
+            let trailhead = std::env::var("trailhead").expect("trailhead must be set as x,y");
+            let (x, y) = trailhead
+                .split_once(',')
+                .expect("trailhead must be set as x,y");
+            let start = (x.trim().parse().unwrap(), y.trim().parse().unwrap());
+            trails::print_trails(&solution::parse(&mode_input()), start, step_rule());
+        }
+        Ok("queries") => {
+            let summit = std::env::var("summit").ok().map(|s| {
+                let (x, y) = s.split_once(',').expect("summit must be set as x,y");
+                (x.trim().parse().unwrap(), y.trim().parse().unwrap())
+            });
+            queries::print_queries(&solution::parse(&mode_input()), step_rule(), summit);
+        }
+        _ => handle_day(),
+    }
+}
+
+fn step_rule() -> trails::StepRule {
+    match std::env::var("max_climb") {
+        Ok(max_climb) => trails::StepRule {
+            max_climb: max_climb.parse().expect("max_climb must be a number"),
+        },
+        Err(_) => trails::StepRule::default(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
@@ -0,0 +1,151 @@
+use crate::{
+    solution::Map,
+    trails::{StepRule, TrailAnalysis, TRAILHEAD},
+};
+
+/// Reachability queries between the trailheads and summits of a map.
+pub struct MapQueries<'a> {
+    map: &'a Map,
+    rule: StepRule,
+    analysis: TrailAnalysis,
+    trailheads: Vec<(usize, usize)>,
+}
+
+impl<'a> MapQueries<'a> {
+    pub fn new(map: &'a Map, rule: StepRule) -> Self {
+        Self {
+            map,
+            rule,
+            analysis: TrailAnalysis::new(map, rule),
+            trailheads: map.find_trailheads(),
+        }
+    }
+
+    pub fn summits(&self) -> &[(usize, usize)] {
+        &self.analysis.summits
+    }
+
+    /// Every trailhead with at least one trail to `summit`.
+    pub fn trailheads_reaching(&self, summit: (usize, usize)) -> Vec<(usize, usize)> {
+        self.trailheads
+            .iter()
+            .filter(|(x, y)| self.analysis.can_reach(*x, *y, summit))
+            .copied()
+            .collect()
+    }
+
+    /// The summit reachable from the most trailheads along with how many, the first in reading order on ties.
+    pub fn most_reachable_summit(&self) -> Option<((usize, usize), usize)> {
+        self.summits()
+            .iter()
+            .map(|summit| (*summit, self.trailheads_reaching(*summit).len()))
+            .rev()
+            .max_by_key(|(_, count)| *count)
+    }
+
+    /// Whether each cell lies on some trail from a trailhead to a summit, indexed `[y][x]`.
+    pub fn on_trail(&self) -> Vec<Vec<bool>> {
+        // Cells reachable from a trailhead, found going up from the trailheads in order of height.
+        let mut from_trailhead = vec![vec![false; self.map.width]; self.map.height];
+        for (x, y) in self.trailheads.iter() {
+            from_trailhead[*y][*x] = true;
+        }
+        for height in TRAILHEAD..=9 {
+            for (x, y) in self.map.cells_at(height) {
+                if !from_trailhead[y][x] {
+                    continue;
+                }
+                for (nx, ny) in self.map.get_neighbours(x, y) {
+                    if self.map.map[ny][nx].is_some_and(|nh| self.rule.allows(height, nh)) {
+                        from_trailhead[ny][nx] = true;
+                    }
+                }
+            }
+        }
+
+        from_trailhead
+            .into_iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(x, reached)| reached && self.analysis.score(x, y) > 0)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The cells that lie on no trail at all, including impassable ones.
+    pub fn unused_cells(&self) -> Vec<(usize, usize)> {
+        self.on_trail()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .filter(|(_, on)| !on)
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect()
+    }
+
+    /// Draws the map with cells on no trail as `.`.
+    /// When a summit is given it is drawn as `*` and the trailheads reaching it as `@`.
+    pub fn render(&self, summit: Option<(usize, usize)>) -> String {
+        let reaching = summit.map_or(vec![], |s| self.trailheads_reaching(s));
+        let on_trail = self.on_trail();
+
+        let mut out = String::new();
+        for (y, row) in self.map.map.iter().enumerate() {
+            for (x, height) in row.iter().enumerate() {
+                let c = match height {
+                    _ if summit == Some((x, y)) => '*',
+                    _ if reaching.contains(&(x, y)) => '@',
+                    Some(h) if on_trail[y][x] => char::from_digit(*h, 10).unwrap(),
+                    _ => '.',
+                };
+                out.push(c);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn format_positions(positions: &[(usize, usize)]) -> String {
+    positions
+        .iter()
+        .map(|(x, y)| format!("({x}, {y})"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Prints the trailheads reaching `summit` (or the most reachable summit if not given) and the overlay rendering.
+pub fn print_queries(map: &Map, rule: StepRule, summit: Option<(usize, usize)>) {
+    let queries = MapQueries::new(map, rule);
+
+    let most_reachable = queries.most_reachable_summit();
+    match most_reachable {
+        Some(((x, y), count)) => {
+            println!("Summit reachable from the most trailheads: ({x}, {y}) from {count}")
+        }
+        None => println!("The map has no summits"),
+    }
+
+    if let Some((x, y)) = summit.filter(|s| !queries.analysis.is_summit(*s)) {
+        panic!("({x}, {y}) is not a summit");
+    }
+
+    let summit = summit.or(most_reachable.map(|(s, _)| s));
+    if let Some((x, y)) = summit {
+        let reaching = queries.trailheads_reaching((x, y));
+        println!(
+            "{} trailheads reach ({x}, {y}): {}",
+            reaching.len(),
+            format_positions(&reaching)
+        );
+    }
+
+    println!("{} cells lie on no trail\n", queries.unused_cells().len());
+    print!("{}", queries.render(summit));
+}
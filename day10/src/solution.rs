@@ -1,27 +1,27 @@
-use std::collections::HashSet;
+use crate::trails::{StepRule, TrailAnalysis, TRAILHEAD};
 
 pub struct Map {
-    map: Vec<Vec<u32>>,
-    width: usize,
-    height: usize,
+    /// Heights, `None` for impassable cells.
+    pub map: Vec<Vec<Option<u32>>>,
+    pub width: usize,
+    pub height: usize,
 }
 
 impl Map {
-    fn find_trailheads(&self) -> Vec<(usize, usize)> {
-        self.map
-            .iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter_map(|(x, &n)| if n == 0 { Some((x, y)) } else { None })
-                    .collect::<Vec<(usize, usize)>>()
-            })
-            .flatten()
-            .collect()
+    pub fn cells_at(&self, height: u32) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.map.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(_, n)| **n == Some(height))
+                .map(move |(x, _)| (x, y))
+        })
     }
 
-    fn get_neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+    pub fn find_trailheads(&self) -> Vec<(usize, usize)> {
+        self.cells_at(TRAILHEAD).collect()
+    }
+
+    pub fn get_neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
         let mut neighbours = vec![];
         if x > 0 {
             neighbours.push((x - 1, y));
@@ -43,10 +43,20 @@ impl Map {
     }
 }
 
-pub fn parse<'a>(input: &'a str) -> Map {
-    let map: Vec<Vec<u32>> = input
+pub fn parse(input: &str) -> Map {
+    let map: Vec<Vec<Option<u32>>> = input
         .lines()
-        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
+        .map(|l| {
+            l.chars()
+                .map(|c| match c {
+                    '.' => None,
+                    c => Some(
+                        c.to_digit(10)
+                            .unwrap_or_else(|| panic!("Unknown map tile {c}")),
+                    ),
+                })
+                .collect()
+        })
         .collect();
 
     let width = map[0].len();
@@ -56,73 +66,23 @@ pub fn parse<'a>(input: &'a str) -> Map {
 }
 
 pub fn solve_part_one<'a>(input: Map) -> String {
-    let trailheads = input.find_trailheads();
-
-    let mut sum = 0;
-    for (th_x, th_y) in trailheads.into_iter() {
-        // println!("Checking trailhead at {th_x} {th_y}");
-        let mut has_checked: HashSet<(usize, usize)> = HashSet::new();
-        let mut to_check = vec![(th_x, th_y)];
-
-        let mut score = 0;
-        while let Some((x, y)) = to_check.pop() {
-            has_checked.insert((x, y));
-            let n = input.map[y][x];
-
-            // println!("Checking {x} {y} ({n})");
-            if n == 9 {
-                score += 1;
-                continue;
-            }
-
-            for (nx, ny) in input.get_neighbours(x, y) {
-                // println!("Checking neighbour {nx} {ny}");
-                if has_checked.contains(&(nx, ny)) {
-                    continue;
-                }
-
-                let nn = input.map[ny][nx];
-                if nn == n + 1 {
-                    to_check.push((nx, ny));
-                }
-            }
-        }
-
-        sum += score;
-    }
-
-    sum.to_string()
+    let analysis = TrailAnalysis::new(&input, StepRule::default());
+
+    input
+        .find_trailheads()
+        .into_iter()
+        .map(|(x, y)| analysis.score(x, y))
+        .sum::<usize>()
+        .to_string()
 }
 
 pub fn solve_part_two<'a>(input: Map) -> String {
-    let trailheads = input.find_trailheads();
-
-    let mut sum = 0;
-    for (th_x, th_y) in trailheads.into_iter() {
-        // println!("Checking trailhead at {th_x} {th_y}");
-        let mut to_check = vec![(th_x, th_y)];
-
-        let mut score = 0;
-        while let Some((x, y)) = to_check.pop() {
-            let n = input.map[y][x];
-
-            // println!("Checking {x} {y} ({n})");
-            if n == 9 {
-                score += 1;
-                continue;
-            }
-
-            for (nx, ny) in input.get_neighbours(x, y) {
-                // println!("Checking neighbour {nx} {ny}");
-                let nn = input.map[ny][nx];
-                if nn == n + 1 {
-                    to_check.push((nx, ny));
-                }
-            }
-        }
-
-        sum += score;
-    }
-
-    sum.to_string()
+    let analysis = TrailAnalysis::new(&input, StepRule::default());
+
+    input
+        .find_trailheads()
+        .into_iter()
+        .map(|(x, y)| analysis.rating(x, y))
+        .sum::<u64>()
+        .to_string()
 }
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::solution::Map;
+
+/// Which steps a hiker may take between neighbouring cells, always strictly uphill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepRule {
+    /// How much higher the next cell may be, 1 for the puzzle's gradual, uphill slope.
+    pub max_climb: u32,
+}
+
+impl Default for StepRule {
+    fn default() -> Self {
+        Self { max_climb: 1 }
+    }
+}
+
+impl StepRule {
+    pub fn allows(&self, from: u32, to: u32) -> bool {
+        to > from && to - from <= self.max_climb
+    }
+}
+
+pub const TRAILHEAD: u32 = 0;
+pub const SUMMIT: u32 = 9;
+
+/// A set of summits, stored as bits indexed by the summit's position in `TrailAnalysis::summits`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SummitSet(Vec<u64>);
+
+impl SummitSet {
+    fn new(summits: usize) -> Self {
+        Self(vec![0; summits.div_ceil(64)])
+    }
+
+    fn insert(&mut self, summit: usize) {
+        self.0[summit / 64] |= 1 << (summit % 64);
+    }
+
+    fn union(&mut self, other: &SummitSet) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a |= b;
+        }
+    }
+
+    fn contains(&self, summit: usize) -> bool {
+        self.0[summit / 64] & (1 << (summit % 64)) != 0
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.0.len() * 64).filter(|i| self.contains(*i))
+    }
+}
+
+/// The summits reachable from every cell, computed in a single pass from the highest cells down.
+pub struct TrailAnalysis {
+    width: usize,
+    pub summits: Vec<(usize, usize)>,
+    /// The index of each summit in `summits`.
+    summit_index: HashMap<(usize, usize), usize>,
+    reachable: Vec<SummitSet>,
+    ratings: Vec<u64>,
+}
+
+impl TrailAnalysis {
+    pub fn new(map: &Map, rule: StepRule) -> Self {
+        let summits: Vec<(usize, usize)> = map.cells_at(SUMMIT).collect();
+
+        let cell_count = map.width * map.height;
+        let mut reachable = vec![SummitSet::new(summits.len()); cell_count];
+        let mut ratings = vec![0u64; cell_count];
+
+        for (i, (x, y)) in summits.iter().enumerate() {
+            reachable[y * map.width + x].insert(i);
+            ratings[y * map.width + x] = 1;
+        }
+
+        // Every allowed step goes uphill, so all cells a step can lead to are done before the cells below them.
+        for height in (0..SUMMIT).rev() {
+            for (x, y) in map.cells_at(height) {
+                let cell = y * map.width + x;
+                for (nx, ny) in map.get_neighbours(x, y) {
+                    let Some(nh) = map.map[ny][nx] else {
+                        continue;
+                    };
+                    if !rule.allows(height, nh) {
+                        continue;
+                    }
+
+                    let neighbour = ny * map.width + nx;
+                    let (set, neighbour_set) = if cell < neighbour {
+                        let (low, high) = reachable.split_at_mut(neighbour);
+                        (&mut low[cell], &high[0])
+                    } else {
+                        let (low, high) = reachable.split_at_mut(cell);
+                        (&mut high[0], &low[neighbour])
+                    };
+                    set.union(neighbour_set);
+                    ratings[cell] += ratings[neighbour];
+                }
+            }
+        }
+
+        Self {
+            width: map.width,
+            summit_index: summits.iter().enumerate().map(|(i, s)| (*s, i)).collect(),
+            summits,
+            reachable,
+            ratings,
+        }
+    }
+
+    /// Number of distinct summits reachable from the cell.
+    pub fn score(&self, x: usize, y: usize) -> usize {
+        self.reachable[y * self.width + x].len()
+    }
+
+    /// Number of distinct trails from the cell to any summit.
+    pub fn rating(&self, x: usize, y: usize) -> u64 {
+        self.ratings[y * self.width + x]
+    }
+
+    pub fn is_summit(&self, summit: (usize, usize)) -> bool {
+        self.summit_index.contains_key(&summit)
+    }
+
+    /// Whether `summit` can be reached from the cell.
+    pub fn can_reach(&self, x: usize, y: usize, summit: (usize, usize)) -> bool {
+        self.summit_index
+            .get(&summit)
+            .is_some_and(|i| self.reachable[y * self.width + x].contains(*i))
+    }
+
+    pub fn reachable_summits(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        self.reachable[y * self.width + x]
+            .iter()
+            .map(|i| self.summits[i])
+            .collect()
+    }
+}
+
+impl Map {
+    /// Every trail, as the list of cells visited, from `start` to any summit.
+    pub fn trails_from(&self, start: (usize, usize), rule: StepRule) -> Vec<Vec<(usize, usize)>> {
+        let mut trails = vec![];
+        let mut trail = vec![start];
+        self.extend_trail(&mut trail, rule, &mut trails);
+        trails
+    }
+
+    fn extend_trail(
+        &self,
+        trail: &mut Vec<(usize, usize)>,
+        rule: StepRule,
+        trails: &mut Vec<Vec<(usize, usize)>>,
+    ) {
+        let (x, y) = *trail.last().unwrap();
+        let Some(height) = self.map[y][x] else {
+            return;
+        };
+
+        if height == SUMMIT {
+            trails.push(trail.clone());
+            return;
+        }
+
+        for (nx, ny) in self.get_neighbours(x, y) {
+            if self.map[ny][nx].is_some_and(|nh| rule.allows(height, nh)) {
+                trail.push((nx, ny));
+                self.extend_trail(trail, rule, trails);
+                trail.pop();
+            }
+        }
+    }
+}
+
+pub fn print_trails(map: &Map, start: (usize, usize), rule: StepRule) {
+    let trails = map.trails_from(start, rule);
+    for trail in trails.iter() {
+        println!(
+            "{}",
+            trail
+                .iter()
+                .map(|(x, y)| format!("({x}, {y})"))
+                .collect::<Vec<String>>()
+                .join(" -> ")
+        );
+    }
+
+    let analysis = TrailAnalysis::new(map, rule);
+    println!(
+        "{} trails, score {}, rating {}",
+        trails.len(),
+        analysis.score(start.0, start.1),
+        analysis.rating(start.0, start.1)
+    );
+    println!(
+        "Reachable summits: {}",
+        analysis
+            .reachable_summits(start.0, start.1)
+            .iter()
+            .map(|(x, y)| format!("({x}, {y})"))
+            .collect::<Vec<String>>()
+            .join(" ")
+    );
+}
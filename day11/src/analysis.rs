@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{bigint::BigUint, rules::RuleSet};
+
+/// Give up building the graph past this many distinct values, the rules probably produce infinitely many.
+const MAX_VALUES: usize = 1_000_000;
+/// Blink counts up to this are counted exactly, larger ones are only estimated from the growth rate.
+/// The count grows by about 1.5x per blink for the puzzle's rules, so after a million blinks it has around 180,000
+/// digits, and the shortest linear recurrence for the puzzle input's counts has an order of about 1,400. Neither
+/// blinking step by step nor exponentiation by squaring of the recurrence gets there in reasonable time with
+/// schoolbook big-integer arithmetic.
+pub const EXACT_LIMIT: usize = 2_000;
+/// The growth rate is considered converged once it changes less than this between blinks.
+const CONVERGENCE: f64 = 1e-12;
+
+/// Every stone value reachable from the initial stones, with edges to the values each one turns into.
+pub struct StoneGraph {
+    pub values: Vec<u128>,
+    /// Outgoing edges by value index, a value splitting into two equal stones has the edge twice.
+    pub edges: Vec<Vec<usize>>,
+    /// How many of each value there are initially.
+    pub initial: Vec<u128>,
+}
+
+impl StoneGraph {
+    pub fn build(rules: &RuleSet, stones: impl Iterator<Item = u128>) -> Result<Self, String> {
+        let mut index: HashMap<u128, usize> = HashMap::new();
+        let mut values = vec![];
+        let mut initial = vec![];
+
+        for stone in stones {
+            let i = *index.entry(stone).or_insert_with(|| {
+                values.push(stone);
+                initial.push(0);
+                values.len() - 1
+            });
+            initial[i] += 1;
+        }
+
+        let mut edges = vec![];
+        while edges.len() < values.len() {
+            if values.len() > MAX_VALUES {
+                return Err(format!(
+                    "More than {MAX_VALUES} distinct stone values, the rules probably never repeat"
+                ));
+            }
+
+            let targets = rules
+                .apply(values[edges.len()])
+                .into_iter()
+                .map(|new| {
+                    *index.entry(new).or_insert_with(|| {
+                        values.push(new);
+                        values.len() - 1
+                    })
+                })
+                .collect();
+            edges.push(targets);
+        }
+
+        initial.resize(values.len(), 0);
+        Ok(Self {
+            values,
+            edges,
+            initial,
+        })
+    }
+
+    /// The first blink after which the set of distinct values present never changes again, and its size.
+    /// `None` if the set still changes after as many blinks as there are values.
+    pub fn fixed_point(&self) -> Option<(usize, usize)> {
+        let mut present: HashSet<usize> = (0..self.values.len())
+            .filter(|i| self.initial[*i] > 0)
+            .collect();
+
+        for blink in 0..=self.values.len() {
+            let next: HashSet<usize> = present
+                .iter()
+                .flat_map(|i| self.edges[*i].iter().copied())
+                .collect();
+            if next == present {
+                return Some((blink, present.len()));
+            }
+            present = next;
+        }
+
+        None
+    }
+
+    /// Estimates the dominant eigenvalue of the transformation by power iteration on the normalised count vector.
+    /// Returns the growth rate per blink along with the blink it converged at and `log10` of the stone count there,
+    /// or `None` if there are no stones to begin with or they all disappear.
+    pub fn growth_rate(&self, max_blinks: usize) -> Option<GrowthRate> {
+        let total: f64 = self.initial.iter().map(|c| *c as f64).sum();
+        if total == 0.0 {
+            return None;
+        }
+
+        let mut counts: Vec<f64> = self.initial.iter().map(|c| *c as f64 / total).collect();
+        let mut log10_total = total.log10();
+        let mut rate = f64::NAN;
+
+        for blink in 1..=max_blinks {
+            let mut next = vec![0.0; counts.len()];
+            for (i, count) in counts.iter().enumerate() {
+                for target in self.edges[i].iter() {
+                    next[*target] += count;
+                }
+            }
+
+            let next_rate: f64 = next.iter().sum();
+            if next_rate == 0.0 {
+                return None;
+            }
+            log10_total += next_rate.log10();
+            counts = next.into_iter().map(|c| c / next_rate).collect();
+
+            if (next_rate - rate).abs() < CONVERGENCE {
+                return Some(GrowthRate {
+                    rate: next_rate,
+                    blink,
+                    log10_total,
+                });
+            }
+            rate = next_rate;
+        }
+
+        Some(GrowthRate {
+            rate,
+            blink: max_blinks,
+            log10_total,
+        })
+    }
+
+    /// The exact number of stones after `blinks` blinks.
+    pub fn exact_count(&self, blinks: usize) -> BigUint {
+        let mut counts: Vec<BigUint> = self.initial.iter().map(|c| BigUint::from(*c)).collect();
+        for _ in 0..blinks {
+            let mut next = vec![BigUint::default(); counts.len()];
+            for (i, count) in counts.iter().enumerate() {
+                if count.is_zero() {
+                    continue;
+                }
+                for target in self.edges[i].iter() {
+                    next[*target].add_assign(count);
+                }
+            }
+            counts = next;
+        }
+
+        let mut total = BigUint::default();
+        for count in counts.iter() {
+            total.add_assign(count);
+        }
+        total
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowthRate {
+    pub rate: f64,
+    pub blink: usize,
+    pub log10_total: f64,
+}
+
+impl GrowthRate {
+    /// An approximation of the number of stones after `blinks` blinks in scientific notation,
+    /// assuming growth by exactly `rate` per blink from the point of convergence.
+    /// Only the leading digits and the magnitude are meaningful, this is not the exact count.
+    pub fn estimate(&self, blinks: usize) -> String {
+        let log10 = self.log10_total + (blinks as f64 - self.blink as f64) * self.rate.log10();
+        let exponent = log10.floor();
+        let mantissa = 10f64.powf(log10 - exponent);
+        format!("{mantissa:.6}e{exponent}")
+    }
+}
+
+pub fn print_analysis(rules: &RuleSet, stones: impl Iterator<Item = u128>, blinks: usize) {
+    let graph = match StoneGraph::build(rules, stones) {
+        Ok(graph) => graph,
+        Err(e) => panic!("{e}"),
+    };
+    println!("{} distinct stone values reachable", graph.values.len());
+
+    match graph.fixed_point() {
+        Some((blink, distinct)) => {
+            println!("Distinct stones stop changing after blink {blink}, at {distinct} values")
+        }
+        None => println!(
+            "Distinct stones still changing after {} blinks",
+            graph.values.len()
+        ),
+    }
+
+    let growth = graph.growth_rate(graph.values.len().max(EXACT_LIMIT));
+    match growth {
+        Some(growth) => println!(
+            "Growth rate {:.12} per blink (converged after {} blinks)",
+            growth.rate, growth.blink
+        ),
+        None => println!("No growth rate, there are no stones left"),
+    }
+
+    if blinks <= EXACT_LIMIT {
+        let count = graph.exact_count(blinks);
+        println!(
+            "Exactly {count} stones after {blinks} blinks ({} digits)",
+            count.digits()
+        );
+    } else {
+        println!(
+            "No exact count after {blinks} blinks, only blink counts up to {EXACT_LIMIT} are counted exactly"
+        );
+        match growth {
+            Some(growth) => println!(
+                "Floating point estimate from the growth rate, not an exact count: roughly {} stones",
+                growth.estimate(blinks)
+            ),
+            None => println!("The stones all disappear, so there are none left after {blinks} blinks"),
+        }
+    }
+}
@@ -2,11 +2,44 @@
 
 use generate_aoc_day::generate_day;
 
+mod analysis;
+mod bigint;
+mod rules;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 11);
 
 fn main() {
-    handle_day();
+    match std::env::var("mode").as_deref() {
+        Ok(mode @ ("stats" | "analysis")) => {
+            let rules = match std::env::var("rules") {
+                Ok(rules) => rules
+                    .parse()
+                    .unwrap_or_else(|e| panic!("Invalid rules: {e}")),
+                Err(_) => rules::RuleSet::puzzle(),
+            };
+            let blinks = std::env::var("blinks")
+                .map(|b| b.parse().expect("blinks must be a number"))
+                .unwrap_or(75);
+
+            let input = mode_input();
+            if mode == "analysis" {
+                analysis::print_analysis(&rules, solution::parse(&input), blinks);
+                return;
+            }
+
+            let mut simulation = rules::Simulation::new(&rules, solution::parse(&input));
+            for stats in simulation.run(blinks) {
+                println!("{stats}");
+            }
+        }
+        _ => handle_day(),
+    }
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
@@ -0,0 +1,221 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// When a rule applies to a stone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    Always,
+    Equals(u128),
+    EvenDigits,
+    OddDigits,
+    DivisibleBy(u128),
+}
+
+/// How one of the stones replacing a stone is computed from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    Value(u128),
+    /// The left half of the stone's digits.
+    LeftHalf,
+    /// The right half of the stone's digits, without leading zeroes.
+    RightHalf,
+    Multiply(u128),
+    Add(u128),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub when: Predicate,
+    pub then: Vec<Output>,
+}
+
+/// Rules are tried in order and the first one that applies replaces the stone.
+/// Stones no rule applies to are left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+pub fn digits(mut n: u128) -> u32 {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+impl Predicate {
+    fn matches(&self, stone: u128) -> bool {
+        match self {
+            Predicate::Always => true,
+            Predicate::Equals(n) => stone == *n,
+            Predicate::EvenDigits => digits(stone).is_multiple_of(2),
+            Predicate::OddDigits => !digits(stone).is_multiple_of(2),
+            Predicate::DivisibleBy(n) => stone.is_multiple_of(*n),
+        }
+    }
+}
+
+impl Output {
+    fn apply(&self, stone: u128) -> u128 {
+        let half = 10u128.pow(digits(stone) / 2);
+        match self {
+            Output::Value(n) => *n,
+            Output::LeftHalf => stone / half,
+            Output::RightHalf => stone % half,
+            Output::Multiply(n) => stone
+                .checked_mul(*n)
+                .unwrap_or_else(|| panic!("Stone {stone} * {n} overflows")),
+            Output::Add(n) => stone
+                .checked_add(*n)
+                .unwrap_or_else(|| panic!("Stone {stone} + {n} overflows")),
+        }
+    }
+}
+
+impl RuleSet {
+    /// The rules from the puzzle.
+    pub fn puzzle() -> Self {
+        "=0 => 1; even => left right; * => *2024".parse().unwrap()
+    }
+
+    /// The stones replacing `stone` after a single blink.
+    pub fn apply(&self, stone: u128) -> Vec<u128> {
+        match self.rules.iter().find(|r| r.when.matches(stone)) {
+            Some(rule) => rule.then.iter().map(|o| o.apply(stone)).collect(),
+            None => vec![stone],
+        }
+    }
+}
+
+impl FromStr for RuleSet {
+    type Err = String;
+
+    /// Rules are separated by `;` and written `predicate => outputs`.
+    /// Predicates are `*` (always), `=N`, `even`, `odd` (number of digits) or `%N` (divisible by N).
+    /// Outputs are separated by spaces and are `N`, `left`, `right` (halves of the digits), `*N` or `+N`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |n: &str| {
+            n.parse::<u128>()
+                .map_err(|_| format!("Invalid number '{n}'"))
+        };
+
+        let rules = s
+            .split(';')
+            .map(|rule| {
+                let (when, then) = rule
+                    .split_once("=>")
+                    .ok_or(format!("Rule '{rule}' is missing '=>'"))?;
+
+                let when = match when.trim() {
+                    "*" => Predicate::Always,
+                    "even" => Predicate::EvenDigits,
+                    "odd" => Predicate::OddDigits,
+                    w if w.starts_with('=') => Predicate::Equals(number(&w[1..])?),
+                    w if w.starts_with('%') => match number(&w[1..])? {
+                        0 => return Err(format!("Predicate '{w}' can't divide by zero")),
+                        n => Predicate::DivisibleBy(n),
+                    },
+                    w => return Err(format!("Unknown predicate '{w}'")),
+                };
+
+                let then = then
+                    .split_whitespace()
+                    .map(|o| match o {
+                        "left" => Ok(Output::LeftHalf),
+                        "right" => Ok(Output::RightHalf),
+                        o if o.starts_with('*') => number(&o[1..]).map(Output::Multiply),
+                        o if o.starts_with('+') => number(&o[1..]).map(Output::Add),
+                        o => number(o).map(Output::Value),
+                    })
+                    .collect::<Result<Vec<Output>, String>>()?;
+
+                Ok(Rule { when, then })
+            })
+            .collect::<Result<Vec<Rule>, String>>()?;
+
+        Ok(Self { rules })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlinkStats {
+    pub blink: usize,
+    /// Number of distinct stone values.
+    pub distinct: usize,
+    /// Total number of stones.
+    pub total: u128,
+}
+
+impl Display for BlinkStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Blink {}: {} stones, {} distinct",
+            self.blink, self.total, self.distinct
+        )
+    }
+}
+
+/// Tracks how many stones there are of each value, as the order of the stones never matters.
+pub struct Simulation<'a> {
+    rules: &'a RuleSet,
+    pub counts: HashMap<u128, u128>,
+    pub blinks: usize,
+    /// The stones each value turns into, as the same values keep coming back.
+    cache: HashMap<u128, Vec<u128>>,
+}
+
+impl<'a> Simulation<'a> {
+    pub fn new(rules: &'a RuleSet, stones: impl Iterator<Item = u128>) -> Self {
+        let mut counts = HashMap::new();
+        for stone in stones {
+            *counts.entry(stone).or_default() += 1;
+        }
+
+        Self {
+            rules,
+            counts,
+            blinks: 0,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn blink(&mut self) {
+        let mut next: HashMap<u128, u128> = HashMap::with_capacity(self.counts.len());
+        for (stone, count) in self.counts.iter() {
+            let replacements = self
+                .cache
+                .entry(*stone)
+                .or_insert_with(|| self.rules.apply(*stone));
+
+            for new in replacements.iter() {
+                let entry = next.entry(*new).or_default();
+                *entry = entry
+                    .checked_add(*count)
+                    .expect("Number of stones overflowed");
+            }
+        }
+
+        self.counts = next;
+        self.blinks += 1;
+    }
+
+    pub fn stats(&self) -> BlinkStats {
+        BlinkStats {
+            blink: self.blinks,
+            distinct: self.counts.len(),
+            total: self.counts.values().sum(),
+        }
+    }
+
+    /// Blinks `blinks` times, returning the statistics after every blink.
+    pub fn run(&mut self, blinks: usize) -> Vec<BlinkStats> {
+        (0..blinks)
+            .map(|_| {
+                self.blink();
+                self.stats()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+
+use crate::regions::Labelling;
+
+/// Fence directions in clockwise order, so turning right is adding one.
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// A closed loop of fence corners, given as grid line intersections with (0, 0) the top-left of the garden.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub points: Vec<(usize, usize)>,
+}
+
+impl Polygon {
+    /// Twice the signed area, positive for loops running clockwise on screen (y pointing down).
+    pub fn signed_double_area(&self) -> isize {
+        let points = &self.points;
+        (0..points.len())
+            .map(|i| {
+                let (x1, y1) = points[i];
+                let (x2, y2) = points[(i + 1) % points.len()];
+                x1 as isize * y2 as isize - x2 as isize * y1 as isize
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outline {
+    pub region: usize,
+    /// Runs clockwise with the region on its right.
+    pub outer: Polygon,
+    /// Each runs anticlockwise, still with the region on its right.
+    pub holes: Vec<Polygon>,
+    /// The first plot of the region in reading order, always inside it.
+    pub anchor: (usize, usize),
+}
+
+impl Outline {
+    /// Every corner is the end of one side, so this matches `Region::sides`.
+    pub fn sides(&self) -> usize {
+        self.outer.points.len() + self.holes.iter().map(|h| h.points.len()).sum::<usize>()
+    }
+}
+
+/// Traces the fences of every region into polygons.
+///
+/// Fence segments are directed so the region is on their right and followed corner to corner. Where a region
+/// touches itself only diagonally, two fences meet at a point; the trace always takes the left turn there, away
+/// from the plot it was following. That splits the outer edge and every hole, even ones touching each other or the
+/// outer edge at a corner, into separate simple loops, and counts the point as two corners, the same as the side
+/// count does.
+pub fn trace_outlines(labelling: &Labelling) -> Vec<Outline> {
+    let mut segments: Vec<HashSet<((usize, usize), usize)>> =
+        vec![HashSet::new(); labelling.regions.len()];
+    let mut anchors = vec![None; labelling.regions.len()];
+
+    for y in 0..labelling.height {
+        for x in 0..labelling.width {
+            let id = labelling.label_at(x, y);
+            anchors[id].get_or_insert((x, y));
+
+            let (sx, sy) = (x as isize, y as isize);
+            let same =
+                |dx: isize, dy: isize| labelling.label_at_signed(sx + dx, sy + dy) == Some(id);
+
+            // The segment along each edge of the plot, going clockwise around it.
+            if !same(0, -1) {
+                segments[id].insert(((x, y), 0));
+            }
+            if !same(1, 0) {
+                segments[id].insert(((x + 1, y), 1));
+            }
+            if !same(0, 1) {
+                segments[id].insert(((x + 1, y + 1), 2));
+            }
+            if !same(-1, 0) {
+                segments[id].insert(((x, y + 1), 3));
+            }
+        }
+    }
+
+    segments
+        .into_iter()
+        .enumerate()
+        .map(|(region, mut segments)| {
+            let mut outer = None;
+            let mut holes = vec![];
+
+            while let Some(start) = segments.iter().min().copied() {
+                let polygon = trace_loop(&mut segments, start);
+                if polygon.signed_double_area() > 0 {
+                    outer = Some(polygon);
+                } else {
+                    holes.push(polygon);
+                }
+            }
+
+            Outline {
+                region,
+                outer: outer.expect("Every region has an outer boundary"),
+                holes,
+                anchor: anchors[region].expect("Every region has a plot"),
+            }
+        })
+        .collect()
+}
+
+/// Follows segments from `start` until back at it, removing them as it goes and recording the corners.
+fn trace_loop(
+    segments: &mut HashSet<((usize, usize), usize)>,
+    start: ((usize, usize), usize),
+) -> Polygon {
+    let mut points = vec![];
+    let (mut pos, mut dir) = start;
+    segments.remove(&start);
+
+    loop {
+        let (dx, dy) = DIRECTIONS[dir];
+        pos = (
+            (pos.0 as isize + dx) as usize,
+            (pos.1 as isize + dy) as usize,
+        );
+
+        // Left, straight on, then right. Turning back would retrace the same plot edge.
+        let next = [(dir + 3) % 4, dir, (dir + 1) % 4]
+            .into_iter()
+            .find(|d| (pos, *d) == start || segments.contains(&(pos, *d)))
+            .expect("Fence segments always form closed loops");
+
+        if next != dir {
+            points.push(pos);
+        }
+        if (pos, next) == start {
+            break;
+        }
+
+        segments.remove(&(pos, next));
+        dir = next;
+    }
+
+    // Start with the top-left corner of the loop, which makes the output stable.
+    let first = (0..points.len())
+        .min_by_key(|i| (points[*i].1, points[*i].0))
+        .unwrap_or(0);
+    points.rotate_left(first);
+
+    Polygon { points }
+}
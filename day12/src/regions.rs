@@ -0,0 +1,189 @@
+use std::fmt::Display;
+
+use crate::solution::Garden;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min_x: usize,
+    pub min_y: usize,
+    pub max_x: usize,
+    pub max_y: usize,
+}
+
+impl BoundingBox {
+    fn at(x: usize, y: usize) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+
+    fn include(&mut self, x: usize, y: usize) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub id: usize,
+    pub plant: char,
+    pub area: usize,
+    pub perimeter: usize,
+    /// Number of straight fence sections, equal to the number of corners.
+    pub sides: usize,
+    pub bounds: BoundingBox,
+}
+
+impl Region {
+    pub fn price(&self) -> usize {
+        self.area * self.perimeter
+    }
+
+    pub fn discounted_price(&self) -> usize {
+        self.area * self.sides
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Region {} of {} plants: area {}, perimeter {}, sides {}, bounds ({}, {})-({}, {}), price {}, discounted {}",
+            self.id,
+            self.plant,
+            self.area,
+            self.perimeter,
+            self.sides,
+            self.bounds.min_x,
+            self.bounds.min_y,
+            self.bounds.max_x,
+            self.bounds.max_y,
+            self.price(),
+            self.discounted_price()
+        )
+    }
+}
+
+/// Every plot labelled with the id of the region it belongs to, region ids being indices into `regions`.
+pub struct Labelling {
+    pub width: usize,
+    pub height: usize,
+    pub labels: Vec<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Labelling {
+    pub fn label_at(&self, x: usize, y: usize) -> usize {
+        self.labels[y * self.width + x]
+    }
+
+    /// The label at a signed position, `None` outside the garden.
+    pub fn label_at_signed(&self, x: isize, y: isize) -> Option<usize> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(self.label_at(x as usize, y as usize))
+    }
+
+    pub fn total_price(&self) -> usize {
+        self.regions.iter().map(Region::price).sum()
+    }
+
+    pub fn total_discounted_price(&self) -> usize {
+        self.regions.iter().map(Region::discounted_price).sum()
+    }
+}
+
+/// Flood fills the garden once, numbering regions in reading order of their first plot,
+/// then measures every region in a single sweep over the label grid.
+pub fn label_regions(garden: &Garden) -> Labelling {
+    let (width, height) = (garden.width, garden.height);
+    let mut labels = vec![usize::MAX; width * height];
+    let mut regions = vec![];
+
+    let mut to_check = vec![];
+    for y in 0..height {
+        for x in 0..width {
+            if labels[y * width + x] != usize::MAX {
+                continue;
+            }
+
+            let id = regions.len();
+            let plant = garden.map[y][x];
+            labels[y * width + x] = id;
+            to_check.push((x, y));
+            while let Some((x, y)) = to_check.pop() {
+                for (nx, ny) in garden.get_neighbours(x, y) {
+                    if labels[ny * width + nx] == usize::MAX && garden.map[ny][nx] == plant {
+                        labels[ny * width + nx] = id;
+                        to_check.push((nx, ny));
+                    }
+                }
+            }
+
+            regions.push(Region {
+                id,
+                plant,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                bounds: BoundingBox::at(x, y),
+            });
+        }
+    }
+
+    let mut labelling = Labelling {
+        width,
+        height,
+        labels,
+        regions: vec![],
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let id = labelling.label_at(x, y);
+            let (sx, sy) = (x as isize, y as isize);
+            let same =
+                |dx: isize, dy: isize| labelling.label_at_signed(sx + dx, sy + dy) == Some(id);
+
+            let region = &mut regions[id];
+            region.area += 1;
+            region.bounds.include(x, y);
+            region.perimeter += [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                .into_iter()
+                .filter(|(dx, dy)| !same(*dx, *dy))
+                .count();
+
+            // Each corner of the plot is checked against the two plots along its edges and the one diagonally across.
+            // It's a convex corner if neither edge plot is in the region, or a concave one if both are but the
+            // diagonal isn't. Two plots touching only diagonally each get their own convex corner.
+            for (dx, dy) in [(1, -1), (1, 1), (-1, 1), (-1, -1)] {
+                let (horizontal, vertical) = (same(dx, 0), same(0, dy));
+                if (!horizontal && !vertical) || (horizontal && vertical && !same(dx, dy)) {
+                    region.sides += 1;
+                }
+            }
+        }
+    }
+
+    labelling.regions = regions;
+    labelling
+}
+
+pub fn print_regions(garden: &Garden) {
+    let labelling = label_regions(garden);
+    for region in labelling.regions.iter() {
+        println!("{region}");
+    }
+    println!(
+        "{} regions, total price {}, discounted price {}",
+        labelling.regions.len(),
+        labelling.total_price(),
+        labelling.total_discounted_price()
+    );
+}
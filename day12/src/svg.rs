@@ -0,0 +1,71 @@
+use std::fmt::Write;
+
+use crate::{
+    outline::{trace_outlines, Polygon},
+    regions::{label_regions, Labelling},
+    solution::Garden,
+};
+
+/// Size in SVG units of a single plot.
+const SCALE: usize = 20;
+
+/// Spreads plant types around the colour wheel so neighbouring letters don't look alike.
+fn plant_hue(plant: char) -> u32 {
+    (plant as u32 * 137) % 360
+}
+
+fn path_data(polygon: &Polygon) -> String {
+    let mut data = String::new();
+    for (i, (x, y)) in polygon.points.iter().enumerate() {
+        let command = if i == 0 { 'M' } else { 'L' };
+        write!(data, "{command}{} {} ", x * SCALE, y * SCALE).unwrap();
+    }
+    data.push('Z');
+    data
+}
+
+/// Renders every region as one filled path, holes cut out with the even-odd rule,
+/// labelled with its side count on its first plot. Hovering a region shows its full report.
+pub fn render_svg(labelling: &Labelling) -> String {
+    let (width, height) = (labelling.width * SCALE, labelling.height * SCALE);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+
+    let mut labels = String::new();
+    for outline in trace_outlines(labelling) {
+        let region = &labelling.regions[outline.region];
+        let data = std::iter::once(&outline.outer)
+            .chain(outline.holes.iter())
+            .map(path_data)
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        writeln!(
+            svg,
+            "  <path d=\"{data}\" fill=\"hsl({}, 70%, 65%)\" fill-rule=\"evenodd\" stroke=\"black\" stroke-width=\"1\"><title>{region}</title></path>",
+            plant_hue(region.plant)
+        )
+        .unwrap();
+
+        let (x, y) = outline.anchor;
+        writeln!(
+            labels,
+            "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+            x * SCALE + SCALE / 2,
+            y * SCALE + SCALE / 2,
+            SCALE / 2,
+            outline.sides()
+        )
+        .unwrap();
+    }
+
+    // Labels go last so no region is drawn over them.
+    svg.push_str(&labels);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn print_svg(garden: &Garden) {
+    print!("{}", render_svg(&label_regions(garden)));
+}
@@ -0,0 +1,328 @@
+use crate::solution::Game;
+
+/// Everything about the claw machines that isn't in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClawConfig {
+    /// Added to both coordinates of every prize.
+    pub offset: i128,
+    /// Tokens per press of button A, must not be negative.
+    pub a_cost: i128,
+    /// Tokens per press of button B, must not be negative.
+    pub b_cost: i128,
+    /// Most presses allowed per button.
+    pub cap: Option<i128>,
+}
+
+pub const PART_ONE: ClawConfig = ClawConfig {
+    offset: 0,
+    a_cost: 3,
+    b_cost: 1,
+    cap: Some(100),
+};
+
+pub const PART_TWO: ClawConfig = ClawConfig {
+    offset: 10_000_000_000_000,
+    a_cost: 3,
+    b_cost: 1,
+    cap: None,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Presses {
+    pub a: i128,
+    pub b: i128,
+}
+
+impl Presses {
+    pub fn cost(&self, config: &ClawConfig) -> i128 {
+        config.a_cost * self.a + config.b_cost * self.b
+    }
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) >= 0` and `a * x + b * y = g`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a.abs(), a.signum(), 0);
+    }
+    let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+    (g, y, x - a.div_euclid(b) * y)
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) == (b < 0) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// Narrows the range of `k` to those where `start + k * step` lies in `0..=cap`.
+/// Returns false if no `k` works.
+fn constrain(
+    lo: &mut Option<i128>,
+    hi: &mut Option<i128>,
+    start: i128,
+    step: i128,
+    cap: Option<i128>,
+) -> bool {
+    let limit = |bound: &mut Option<i128>, value: i128, tighter: fn(i128, i128) -> i128| {
+        *bound = Some(bound.map_or(value, |b| tighter(b, value)));
+    };
+
+    match step.signum() {
+        0 => start >= 0 && cap.is_none_or(|cap| start <= cap),
+        1 => {
+            limit(lo, div_ceil(-start, step), i128::max);
+            if let Some(cap) = cap {
+                limit(hi, div_floor(cap - start, step), i128::min);
+            }
+            true
+        }
+        _ => {
+            limit(hi, div_floor(-start, step), i128::min);
+            if let Some(cap) = cap {
+                limit(lo, div_ceil(cap - start, step), i128::max);
+            }
+            true
+        }
+    }
+}
+
+/// Cheapest non-negative `a, b` with `a * p + b * q = r`, each at most the cap if given.
+/// At least one of `p` and `q` must be non-zero.
+fn cheapest_on_line(p: i128, q: i128, r: i128, config: &ClawConfig) -> Option<Presses> {
+    let (g, x, y) = extended_gcd(p, q);
+    if r % g != 0 {
+        return None;
+    }
+
+    // Every solution is (a0 + k * step_a, b0 + k * step_b) for integer k.
+    let (a0, b0) = (x * (r / g), y * (r / g));
+    let (step_a, step_b) = (q / g, -p / g);
+
+    let (mut lo, mut hi) = (None, None);
+    if !constrain(&mut lo, &mut hi, a0, step_a, config.cap)
+        || !constrain(&mut lo, &mut hi, b0, step_b, config.cap)
+    {
+        return None;
+    }
+
+    // The cost is linear in k, so the cheapest solution is at one end of the range.
+    // Non-negative presses and costs keep the range bounded on the side the cost falls towards.
+    let slope = config.a_cost * step_a + config.b_cost * step_b;
+    let k = match (lo, hi) {
+        (Some(lo), Some(hi)) if lo > hi => return None,
+        (Some(lo), _) if slope >= 0 => lo,
+        (_, Some(hi)) => hi,
+        (Some(lo), None) => lo,
+        (None, None) => unreachable!("At least one button moves along the line"),
+    };
+
+    Some(Presses {
+        a: a0 + k * step_a,
+        b: b0 + k * step_b,
+    })
+}
+
+/// Finds the cheapest way to reach the prize moved by the configured offset on both axes,
+/// pressing each button at most the configured cap if there is one.
+///
+/// When the buttons aren't parallel there's at most one way to reach the prize. When they are, the prize must lie
+/// on their shared line and the problem reduces to a single linear Diophantine equation along one axis.
+pub fn cheapest_win(game: &Game, config: &ClawConfig) -> Option<Presses> {
+    let (offset, cap) = (config.offset, config.cap);
+    let (prize_x, prize_y) = (game.prize_x + offset, game.prize_y + offset);
+    let divisor = game.a_x * game.b_y - game.a_y * game.b_x;
+
+    if divisor != 0 {
+        let a_dividend = prize_x * game.b_y - prize_y * game.b_x;
+        let b_dividend = prize_y * game.a_x - prize_x * game.a_y;
+        if a_dividend % divisor != 0 || b_dividend % divisor != 0 {
+            return None;
+        }
+
+        let presses = Presses {
+            a: a_dividend / divisor,
+            b: b_dividend / divisor,
+        };
+        let in_range = |n: i128| n >= 0 && cap.is_none_or(|cap| n <= cap);
+        return (in_range(presses.a) && in_range(presses.b)).then_some(presses);
+    }
+
+    // Both buttons lie on one line through the origin, the prize has to as well.
+    let cross = |x: i128, y: i128| x * prize_y - y * prize_x;
+    if cross(game.a_x, game.a_y) != 0 || cross(game.b_x, game.b_y) != 0 {
+        return None;
+    }
+
+    // Any axis the buttons move along holds the whole equation.
+    if game.a_x != 0 || game.b_x != 0 {
+        cheapest_on_line(game.a_x, game.b_x, prize_x, config)
+    } else if game.a_y != 0 || game.b_y != 0 {
+        cheapest_on_line(game.a_y, game.b_y, prize_y, config)
+    } else {
+        // Neither button moves the claw at all.
+        (prize_x == 0 && prize_y == 0).then_some(Presses { a: 0, b: 0 })
+    }
+}
+
+/// The total cost of winning every winnable machine.
+pub fn total_cost(games: impl Iterator<Item = Game>, config: &ClawConfig) -> i128 {
+    games
+        .filter_map(|game| cheapest_win(&game, config))
+        .map(|presses| presses.cost(config))
+        .sum()
+}
+
+pub fn print_machines(games: impl Iterator<Item = Game>, config: &ClawConfig) {
+    let (mut won, mut total) = (0, 0);
+    for (i, game) in games.enumerate() {
+        match cheapest_win(&game, config) {
+            Some(presses) => {
+                let cost = presses.cost(config);
+                println!(
+                    "Machine {}: A {} B {} cost {cost}",
+                    i + 1,
+                    presses.a,
+                    presses.b
+                );
+                won += 1;
+                total += cost;
+            }
+            None => println!("Machine {}: unwinnable", i + 1),
+        }
+    }
+    println!("{won} prizes won for {total} tokens");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(a: (i128, i128), b: (i128, i128), prize: (i128, i128)) -> Game {
+        Game {
+            a_x: a.0,
+            a_y: a.1,
+            b_x: b.0,
+            b_y: b.1,
+            prize_x: prize.0,
+            prize_y: prize.1,
+        }
+    }
+
+    fn cost(game: &Game, config: &ClawConfig) -> Option<i128> {
+        cheapest_win(game, config).map(|presses| presses.cost(config))
+    }
+
+    #[test]
+    fn independent_buttons_have_a_single_solution() {
+        let game = game((94, 34), (22, 67), (8400, 5400));
+        assert_eq!(
+            cheapest_win(&game, &PART_ONE),
+            Some(Presses { a: 80, b: 40 })
+        );
+        assert_eq!(cost(&game, &PART_ONE), Some(280));
+    }
+
+    #[test]
+    fn independent_buttons_need_whole_presses() {
+        let game = game((26, 66), (67, 21), (12748, 12176));
+        assert_eq!(cheapest_win(&game, &PART_ONE), None);
+    }
+
+    #[test]
+    fn negative_presses_are_rejected() {
+        let game = game((1, 0), (0, 1), (-1, 5));
+        assert_eq!(cheapest_win(&game, &PART_ONE), None);
+    }
+
+    #[test]
+    fn cap_limits_presses_of_each_button() {
+        let game = game((1, 0), (0, 1), (101, 5));
+        assert_eq!(cheapest_win(&game, &PART_ONE), None);
+
+        let uncapped = ClawConfig {
+            offset: 0,
+            ..PART_TWO
+        };
+        assert_eq!(
+            cheapest_win(&game, &uncapped),
+            Some(Presses { a: 101, b: 5 })
+        );
+        assert_eq!(cost(&game, &uncapped), Some(308));
+    }
+
+    #[test]
+    fn collinear_buttons_pick_the_cheapest_combination() {
+        let game = game((1, 1), (2, 2), (10, 10));
+        assert_eq!(cheapest_win(&game, &PART_ONE), Some(Presses { a: 0, b: 5 }));
+        assert_eq!(cost(&game, &PART_ONE), Some(5));
+
+        let a_cheaper = ClawConfig {
+            a_cost: 1,
+            b_cost: 3,
+            ..PART_ONE
+        };
+        assert_eq!(
+            cheapest_win(&game, &a_cheaper),
+            Some(Presses { a: 10, b: 0 })
+        );
+        assert_eq!(cost(&game, &a_cheaper), Some(10));
+    }
+
+    #[test]
+    fn collinear_buttons_respect_the_cap() {
+        let game = game((1, 1), (2, 2), (10, 10));
+        let capped = ClawConfig {
+            cap: Some(4),
+            ..PART_ONE
+        };
+        assert_eq!(cheapest_win(&game, &capped), Some(Presses { a: 2, b: 4 }));
+        assert_eq!(cost(&game, &capped), Some(10));
+    }
+
+    #[test]
+    fn collinear_buttons_need_the_prize_on_their_line() {
+        let game = game((1, 1), (2, 2), (3, 4));
+        assert_eq!(cheapest_win(&game, &PART_ONE), None);
+    }
+
+    #[test]
+    fn collinear_buttons_along_one_axis() {
+        let reachable = game((0, 3), (0, 5), (0, 11));
+        assert_eq!(
+            cheapest_win(&reachable, &PART_ONE),
+            Some(Presses { a: 2, b: 1 })
+        );
+        assert_eq!(cost(&reachable, &PART_ONE), Some(7));
+
+        let unreachable = game((0, 3), (0, 5), (0, 7));
+        assert_eq!(cheapest_win(&unreachable, &PART_ONE), None);
+    }
+
+    #[test]
+    fn zero_button_is_never_pressed() {
+        let game = game((0, 0), (2, 3), (4, 6));
+        assert_eq!(cheapest_win(&game, &PART_ONE), Some(Presses { a: 0, b: 2 }));
+        assert_eq!(cost(&game, &PART_ONE), Some(2));
+    }
+
+    #[test]
+    fn zero_buttons_only_win_a_prize_at_the_start() {
+        assert_eq!(
+            cheapest_win(&game((0, 0), (0, 0), (0, 0)), &PART_ONE),
+            Some(Presses { a: 0, b: 0 })
+        );
+        assert_eq!(cheapest_win(&game((0, 0), (0, 0), (1, 0)), &PART_ONE), None);
+    }
+}
@@ -2,11 +2,69 @@
 
 use generate_aoc_day::generate_day;
 
+mod claw;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 13);
 
 fn main() {
-    handle_day();
+    let config = config();
+    match std::env::var("mode").as_deref() {
+        Ok("machines") => claw::print_machines(solution::parse(&mode_input()), &config),
+        // The generated solvers use the puzzle's settings, so runs with overrides are solved here.
+        _ if config != part_config() => {
+            println!(
+                "{}",
+                claw::total_cost(solution::parse(&mode_input()), &config)
+            )
+        }
+        _ => handle_day(),
+    }
+}
+
+/// The chosen part's configuration as in the puzzle.
+fn part_config() -> claw::ClawConfig {
+    match std::env::var("part").as_deref() {
+        Ok("part2") => claw::PART_TWO,
+        _ => claw::PART_ONE,
+    }
+}
+
+/// The chosen part's configuration with any overrides from the environment.
+fn config() -> claw::ClawConfig {
+    let mut config = part_config();
+
+    let number = |name: &str| {
+        std::env::var(name).ok().map(|n| {
+            n.parse::<i128>()
+                .unwrap_or_else(|_| panic!("{name} must be a number"))
+        })
+    };
+    if let Some(offset) = number("offset") {
+        config.offset = offset;
+    }
+    if let Some(a_cost) = number("a_cost") {
+        config.a_cost = a_cost;
+    }
+    if let Some(b_cost) = number("b_cost") {
+        config.b_cost = b_cost;
+    }
+    match std::env::var("cap").as_deref() {
+        Ok("none") => config.cap = None,
+        Ok(cap) => config.cap = Some(cap.parse().expect("cap must be a number or none")),
+        Err(_) => {}
+    }
+
+    assert!(
+        config.a_cost >= 0 && config.b_cost >= 0,
+        "Token costs must not be negative"
+    );
+    config
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
@@ -0,0 +1,259 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::{room::Room, solution::Robot};
+
+/// Side of the square blocks robots are binned into for the entropy score.
+const ENTROPY_BLOCK: i64 = 5;
+
+/// How tree-like a second looks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scoring {
+    /// Sum of the x and y variance of robot positions. Only the seconds combining the lowest variance offsets
+    /// along each axis are considered, as each axis repeats with its own period.
+    Variance,
+    /// Shannon entropy in bits of how robots are spread over blocks of the room.
+    Entropy,
+    /// Number of robot cells in the largest group of orthogonally touching ones.
+    Component,
+    /// The puzzle's safety factor, low when most robots crowd into one quadrant.
+    Safety,
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "variance" => Ok(Scoring::Variance),
+            "entropy" => Ok(Scoring::Entropy),
+            "component" => Ok(Scoring::Component),
+            "safety" => Ok(Scoring::Safety),
+            other => Err(format!(
+                "Unknown scoring '{other}', expected variance, entropy, component or safety"
+            )),
+        }
+    }
+}
+
+impl Display for Scoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Scoring::Variance => "variance",
+            Scoring::Entropy => "entropy",
+            Scoring::Component => "component",
+            Scoring::Safety => "safety",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Scoring {
+    pub fn lower_is_better(&self) -> bool {
+        !matches!(self, Scoring::Component)
+    }
+
+    pub fn score(&self, room: &Room, positions: &[(i64, i64)]) -> f64 {
+        match self {
+            Scoring::Variance => {
+                variance(positions.iter().map(|p| p.0)) + variance(positions.iter().map(|p| p.1))
+            }
+            Scoring::Entropy => entropy(positions),
+            Scoring::Component => largest_component(room, positions) as f64,
+            Scoring::Safety => room.safety_factor(positions) as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candidate {
+    pub time: i64,
+    pub score: f64,
+}
+
+fn variance(values: impl Iterator<Item = i64>) -> f64 {
+    let (mut n, mut sum, mut sum_sq) = (0.0, 0.0, 0.0);
+    for v in values {
+        n += 1.0;
+        sum += v as f64;
+        sum_sq += (v * v) as f64;
+    }
+    if n == 0.0 {
+        return 0.0;
+    }
+    let mean = sum / n;
+    sum_sq / n - mean * mean
+}
+
+fn entropy(positions: &[(i64, i64)]) -> f64 {
+    let mut blocks: HashMap<(i64, i64), usize> = HashMap::new();
+    for (x, y) in positions.iter() {
+        *blocks
+            .entry((x / ENTROPY_BLOCK, y / ENTROPY_BLOCK))
+            .or_default() += 1;
+    }
+
+    let total = positions.len() as f64;
+    blocks
+        .values()
+        .map(|count| {
+            let p = *count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn largest_component(room: &Room, positions: &[(i64, i64)]) -> usize {
+    let density = room.density(positions);
+    let mut seen = vec![vec![false; room.width as usize]; room.height as usize];
+    let mut largest = 0;
+
+    for (x, y) in positions.iter() {
+        let (x, y) = (*x as usize, *y as usize);
+        if seen[y][x] {
+            continue;
+        }
+
+        seen[y][x] = true;
+        let mut size = 0;
+        let mut to_check = vec![(x, y)];
+        while let Some((x, y)) = to_check.pop() {
+            size += 1;
+            for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+                if nx < 0 || ny < 0 || nx >= room.width || ny >= room.height {
+                    continue;
+                }
+                let (nx, ny) = (nx as usize, ny as usize);
+                if density[ny][nx] > 0 && !seen[ny][nx] {
+                    seen[ny][nx] = true;
+                    to_check.push((nx, ny));
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+
+    largest
+}
+
+/// Variance of the x positions for each second in `0..width`, and of the y positions for each in `0..height`.
+/// Each axis moves independently and repeats with its own period, so this covers every second.
+pub fn axis_variances(robots: &[Robot], room: &Room) -> (Vec<f64>, Vec<f64>) {
+    let x = (0..room.width)
+        .map(|t| variance(robots.iter().map(|r| room.position_at(r, t).0)))
+        .collect();
+    let y = (0..room.height)
+        .map(|t| variance(robots.iter().map(|r| room.position_at(r, t).1)))
+        .collect();
+    (x, y)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+/// The smallest non-negative `t` with `t ≡ a (mod m)` and `t ≡ b (mod n)`, if there is one.
+/// The moduli don't need to be coprime.
+pub fn crt(a: i64, m: i64, b: i64, n: i64) -> Option<i64> {
+    let (g, p, _) = extended_gcd(m, n);
+    if (b - a) % g != 0 {
+        return None;
+    }
+
+    let lcm = m / g * n;
+    let k = ((b - a) / g * p).rem_euclid(n / g);
+    Some((a + m * k).rem_euclid(lcm))
+}
+
+/// Offsets in `0..values.len()` ordered from lowest value to highest.
+fn ranked_offsets(values: &[f64]) -> Vec<i64> {
+    let mut offsets: Vec<i64> = (0..values.len() as i64).collect();
+    offsets.sort_by(|a, b| values[*a as usize].total_cmp(&values[*b as usize]));
+    offsets
+}
+
+/// The `top` most tree-like seconds within one period of the room, best first.
+/// Never empty when `top` is positive.
+pub fn rank_candidates(
+    robots: &[Robot],
+    room: &Room,
+    scoring: Scoring,
+    top: usize,
+) -> Vec<Candidate> {
+    let times: Vec<i64> = match scoring {
+        Scoring::Variance => {
+            // The best `top` seconds combine offsets within the best `top` of each axis.
+            let (x, y) = axis_variances(robots, room);
+            let (x, y) = (ranked_offsets(&x), ranked_offsets(&y));
+            x.iter()
+                .take(top)
+                .flat_map(|tx| {
+                    y.iter()
+                        .take(top)
+                        .filter_map(|ty| crt(*tx, room.width, *ty, room.height))
+                })
+                .collect()
+        }
+        _ => vec![],
+    };
+    // Sizes sharing a factor can leave the best offsets of the two axes without a common second,
+    // in which case every second gets scored instead.
+    let times = if times.len() < top {
+        (0..room.period()).collect()
+    } else {
+        times
+    };
+
+    let mut candidates: Vec<Candidate> = times
+        .into_iter()
+        .map(|time| Candidate {
+            time,
+            score: scoring.score(room, &room.positions_at(robots, time)),
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        let order = a.score.total_cmp(&b.score);
+        let order = if scoring.lower_is_better() {
+            order
+        } else {
+            order.reverse()
+        };
+        order.then(a.time.cmp(&b.time))
+    });
+    candidates.truncate(top);
+    candidates
+}
+
+pub fn print_candidates(robots: &[Robot], room: Room, scoring: Scoring, top: usize) {
+    if scoring == Scoring::Variance {
+        let (x, y) = axis_variances(robots, &room);
+        let (tx, ty) = (ranked_offsets(&x)[0], ranked_offsets(&y)[0]);
+        println!(
+            "Lowest x variance {:.2} at t = {tx} (mod {})",
+            x[tx as usize], room.width
+        );
+        println!(
+            "Lowest y variance {:.2} at t = {ty} (mod {})",
+            y[ty as usize], room.height
+        );
+    }
+
+    println!("{:>4}  {:>8}  {:>12}", "rank", "time", scoring);
+    for (i, candidate) in rank_candidates(robots, &room, scoring, top)
+        .iter()
+        .enumerate()
+    {
+        println!(
+            "{:>4}  {:>8}  {:>12.4}",
+            i + 1,
+            candidate.time,
+            candidate.score
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{room::Room, solution::Robot};
+
+/// Pixels between frames on a contact sheet.
+const SHEET_GAP: usize = 2;
+/// Grey level of the gaps between frames on a contact sheet, light enough to come out white in PBM.
+const SHEET_GAP_GREY: u8 = 200;
+/// Grey levels below this are black in PBM, i.e. any cell with a robot on it.
+const PBM_THRESHOLD: u8 = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Black and white, black wherever there is at least one robot.
+    Pbm,
+    /// Greyscale on white, each extra robot on a cell halving its brightness.
+    Pgm,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbm" => Ok(Format::Pbm),
+            "pgm" => Ok(Format::Pgm),
+            other => Err(format!("Unknown format '{other}', expected pbm or pgm")),
+        }
+    }
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Pbm => "pbm",
+            Format::Pgm => "pgm",
+        }
+    }
+}
+
+/// Seconds `from..to` taking every `step`th one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub from: i64,
+    pub to: i64,
+    pub step: i64,
+}
+
+impl TimeRange {
+    /// `step` must be positive, which `main` checks when reading it.
+    pub fn times(&self) -> impl Iterator<Item = i64> {
+        assert!(self.step > 0, "step must be positive");
+        (self.from..self.to).step_by(self.step as usize)
+    }
+}
+
+/// Grey level of a cell, white when empty with each robot on it halving the brightness.
+fn grey(robots: usize) -> u8 {
+    (255u32 >> robots.min(8)) as u8
+}
+
+/// Binary PBM (P4) or PGM (P5) header.
+fn header(format: Format, width: usize, height: usize) -> String {
+    match format {
+        Format::Pbm => format!("P4\n{width} {height}\n"),
+        Format::Pgm => format!("P5\n{width} {height}\n255\n"),
+    }
+}
+
+/// Writes rows of grey levels, `width` pixels each, in the format's binary encoding.
+fn write_rows(out: &mut impl Write, format: Format, width: usize, pixels: &[u8]) -> io::Result<()> {
+    match format {
+        Format::Pbm => {
+            let mut packed = Vec::with_capacity(width.div_ceil(8));
+            for row in pixels.chunks(width) {
+                // Rows are packed eight pixels to a byte, most significant bit first, 1 being black.
+                packed.clear();
+                packed.extend(row.chunks(8).map(|byte| {
+                    byte.iter().enumerate().fold(0u8, |bits, (i, grey)| {
+                        bits | (((*grey < PBM_THRESHOLD) as u8) << (7 - i))
+                    })
+                }));
+                out.write_all(&packed)?;
+            }
+            Ok(())
+        }
+        Format::Pgm => out.write_all(pixels),
+    }
+}
+
+/// The grey level of every cell of the room, row by row.
+fn frame(room: &Room, robots: &[Robot], t: i64) -> Vec<u8> {
+    room.density(&room.positions_at(robots, t))
+        .into_iter()
+        .flatten()
+        .map(grey)
+        .collect()
+}
+
+/// Writes one image per second in the range to `dir`, named after the second. Returns how many were written.
+pub fn export_frames(
+    robots: &[Robot],
+    room: &Room,
+    range: TimeRange,
+    format: Format,
+    dir: &Path,
+) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    let (width, height) = (room.width as usize, room.height as usize);
+
+    let mut written = 0;
+    for t in range.times() {
+        let path = dir.join(format!("frame_{t:06}.{}", format.extension()));
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(header(format, width, height).as_bytes())?;
+        write_rows(&mut out, format, width, &frame(room, robots, t))?;
+        out.flush()?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+/// Writes every second in the range as one image, frames tiled left to right then top to bottom
+/// in rows of `columns` (roughly square if not given). Returns the number of columns and rows.
+/// The sheet is written one row of frames at a time, so only that much of it is ever held in memory.
+pub fn export_sheet(
+    robots: &[Robot],
+    room: &Room,
+    range: TimeRange,
+    format: Format,
+    columns: Option<usize>,
+    path: &Path,
+) -> io::Result<(usize, usize)> {
+    let times: Vec<i64> = range.times().collect();
+    let columns = columns
+        .unwrap_or_else(|| (times.len() as f64).sqrt().ceil() as usize)
+        .max(1);
+    let rows = times.len().div_ceil(columns).max(1);
+
+    let (frame_width, frame_height) = (room.width as usize, room.height as usize);
+    let width = columns * (frame_width + SHEET_GAP) - SHEET_GAP;
+    let height = rows * (frame_height + SHEET_GAP) - SHEET_GAP;
+
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(header(format, width, height).as_bytes())?;
+
+    let gap = vec![SHEET_GAP_GREY; width * SHEET_GAP];
+    let mut band = vec![SHEET_GAP_GREY; width * frame_height];
+    for (row, times) in times.chunks(columns).enumerate() {
+        if row > 0 {
+            write_rows(&mut out, format, width, &gap)?;
+        }
+
+        band.fill(SHEET_GAP_GREY);
+        for (column, t) in times.iter().enumerate() {
+            let left = column * (frame_width + SHEET_GAP);
+            for (y, line) in frame(room, robots, *t).chunks(frame_width).enumerate() {
+                let start = y * width + left;
+                band[start..start + frame_width].copy_from_slice(line);
+            }
+        }
+        write_rows(&mut out, format, width, &band)?;
+    }
+
+    // An empty range still gets a blank sheet of the promised size.
+    if times.is_empty() {
+        write_rows(&mut out, format, width, &band)?;
+    }
+
+    out.flush()?;
+    Ok((columns, rows))
+}
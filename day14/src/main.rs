@@ -1,12 +1,132 @@
 #![feature(test)]
 
+use std::path::Path;
+
 use generate_aoc_day::generate_day;
 
+mod detect;
+mod export;
+mod room;
 mod solution;
 
 // Replace with desired year/day
 generate_day!(2024, 14);
 
 fn main() {
-    handle_day();
+    let config = room_config().unwrap_or_else(|e| fail(&e));
+
+    match std::env::var("mode").as_deref() {
+        Ok("positions") => {
+            let t = std::env::var("t")
+                .expect("t must be set to a number of seconds")
+                .parse()
+                .expect("t must be a number");
+            let robots = solution::parse(&mode_input());
+            room::print_positions(&robots, mode_room(&robots, &config), t);
+        }
+        Ok("detect") => {
+            let scoring = match std::env::var("scoring") {
+                Ok(scoring) => scoring.parse().unwrap_or_else(|e| panic!("{e}")),
+                Err(_) => detect::Scoring::Variance,
+            };
+            let top = std::env::var("top")
+                .map(|top| top.parse().expect("top must be a number"))
+                .unwrap_or(5);
+            let robots = solution::parse(&mode_input());
+            detect::print_candidates(&robots, mode_room(&robots, &config), scoring, top);
+        }
+        Ok(mode @ ("frames" | "sheet")) => {
+            let robots = solution::parse(&mode_input());
+            let room = mode_room(&robots, &config);
+            let number = |name: &str, default: i64| match std::env::var(name) {
+                Ok(n) => n
+                    .parse()
+                    .unwrap_or_else(|_| fail(&format!("{name} must be a number, got '{n}'"))),
+                Err(_) => default,
+            };
+            let range = export::TimeRange {
+                from: number("from", 0),
+                to: number("to", room.period()),
+                step: number("step", 1),
+            };
+            if range.step <= 0 {
+                fail(&format!("step must be positive, got {}", range.step));
+            }
+            let format = match std::env::var("format") {
+                Ok(format) => format.parse().unwrap_or_else(|e| panic!("{e}")),
+                Err(_) => export::Format::Pgm,
+            };
+
+            if mode == "frames" {
+                let dir = std::env::var("output").unwrap_or_else(|_| String::from("frames"));
+                let written = export::export_frames(&robots, &room, range, format, Path::new(&dir))
+                    .unwrap_or_else(|e| panic!("Failed to write frames to {dir}: {e}"));
+                println!("Wrote {written} frames to {dir}");
+            } else {
+                let path = std::env::var("output")
+                    .unwrap_or_else(|_| format!("sheet.{}", format.extension()));
+                let columns = std::env::var("columns")
+                    .ok()
+                    .map(|c| c.parse().expect("columns must be a number"));
+                let (columns, rows) =
+                    export::export_sheet(&robots, &room, range, format, columns, Path::new(&path))
+                        .unwrap_or_else(|e| panic!("Failed to write contact sheet {path}: {e}"));
+                println!(
+                    "Wrote {columns}x{rows} frames to {path}, starting at {} seconds and stepping {} per frame",
+                    range.from, range.step
+                );
+            }
+        }
+        // The generated solvers always use the default room, so runs with overrides are solved here.
+        _ if config != room::RoomConfig::default() => {
+            let robots = solution::parse(&mode_input());
+            let answer = match std::env::var("part").as_deref() {
+                Ok("part2") => solution::tree_time(&robots, &config).map(|t| t.to_string()),
+                _ => solution::safety_factor(&robots, &config).map(|f| f.to_string()),
+            };
+            println!("{}", answer.unwrap_or_else(|e| fail(&e)));
+        }
+        _ => handle_day(),
+    }
+}
+
+/// Reports a bad configuration and exits.
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+/// Reads the room settings from the `width`, `height` and `rounds` environment variables.
+fn room_config() -> Result<room::RoomConfig, String> {
+    let number = |name: &str| match std::env::var(name) {
+        Ok(n) => n
+            .parse::<i64>()
+            .map(Some)
+            .map_err(|_| format!("{name} must be a number, got '{n}'")),
+        Err(_) => Ok(None),
+    };
+    let size = |name: &str| match number(name)? {
+        Some(size) if size <= 0 => Err(format!("{name} must be positive, got {size}")),
+        size => Ok(size),
+    };
+
+    let mut config = room::RoomConfig {
+        width: size("width")?,
+        height: size("height")?,
+        ..Default::default()
+    };
+    if let Some(rounds) = number("rounds")? {
+        config.rounds = rounds;
+    }
+    Ok(config)
+}
+
+fn mode_room(robots: &[solution::Robot], config: &room::RoomConfig) -> room::Room {
+    room::Room::for_robots(robots, config).unwrap_or_else(|e| fail(&e))
+}
+
+fn mode_input() -> String {
+    let path = std::env::var("test_file").unwrap_or_else(|_| String::from("input.txt"));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read input file {path}: {e}"))
 }
@@ -0,0 +1,161 @@
+use crate::solution::Robot;
+
+const NUM_ROUNDS: i64 = 100;
+
+/// Room settings that aren't in the input. `main` reads them from the environment and passes them along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoomConfig {
+    /// Inferred from the robots if not set.
+    pub width: Option<i64>,
+    /// Inferred from the robots if not set.
+    pub height: Option<i64>,
+    /// How many seconds part one simulates.
+    pub rounds: i64,
+}
+
+impl Default for RoomConfig {
+    fn default() -> Self {
+        Self {
+            width: None,
+            height: None,
+            rounds: NUM_ROUNDS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Room {
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Room {
+    pub const PUZZLE: Room = Room {
+        width: 101,
+        height: 103,
+    };
+    pub const EXAMPLE: Room = Room {
+        width: 11,
+        height: 7,
+    };
+
+    /// The smallest known room that fits every robot, or one just large enough for them if none does.
+    pub fn infer(robots: &[Robot]) -> Room {
+        let max_x = robots.iter().map(|r| r.pos_x).max().unwrap_or(0);
+        let max_y = robots.iter().map(|r| r.pos_y).max().unwrap_or(0);
+
+        [Room::EXAMPLE, Room::PUZZLE]
+            .into_iter()
+            .find(|room| max_x < room.width && max_y < room.height)
+            .unwrap_or(Room {
+                width: max_x + 1,
+                height: max_y + 1,
+            })
+    }
+
+    /// The configured room, inferring any size that isn't set from the robots.
+    /// Fails if a robot starts outside it.
+    pub fn for_robots(robots: &[Robot], config: &RoomConfig) -> Result<Room, String> {
+        let inferred = Room::infer(robots);
+        let room = Room {
+            width: config.width.unwrap_or(inferred.width),
+            height: config.height.unwrap_or(inferred.height),
+        };
+
+        match robots
+            .iter()
+            .find(|r| r.pos_x < 0 || r.pos_x >= room.width || r.pos_y < 0 || r.pos_y >= room.height)
+        {
+            Some(r) => Err(format!(
+                "Robot at {},{} starts outside the {}x{} room",
+                r.pos_x, r.pos_y, room.width, room.height
+            )),
+            None => Ok(room),
+        }
+    }
+
+    /// Every robot is back where it started after this many seconds.
+    pub fn period(&self) -> i64 {
+        self.width * self.height / gcd(self.width, self.height)
+    }
+
+    /// Where the robot is after `t` seconds, negative `t` running time backwards.
+    /// Each axis repeats with the room's size, so `t` is reduced first and any `t` is fine.
+    pub fn position_at(&self, robot: &Robot, t: i64) -> (i64, i64) {
+        let x = robot.pos_x + robot.vel_x.rem_euclid(self.width) * t.rem_euclid(self.width);
+        let y = robot.pos_y + robot.vel_y.rem_euclid(self.height) * t.rem_euclid(self.height);
+        (x.rem_euclid(self.width), y.rem_euclid(self.height))
+    }
+
+    pub fn positions_at(&self, robots: &[Robot], t: i64) -> Vec<(i64, i64)> {
+        robots
+            .iter()
+            .map(|robot| self.position_at(robot, t))
+            .collect()
+    }
+
+    /// Product of the number of robots in each quadrant.
+    /// An odd size has a middle line that robots on don't count, an even size splits evenly with no middle line.
+    pub fn safety_factor(&self, positions: &[(i64, i64)]) -> usize {
+        let half = |v: i64, size: i64| {
+            if size % 2 == 1 && v == size / 2 {
+                None
+            } else {
+                Some((v >= size / 2) as usize)
+            }
+        };
+
+        let mut quadrants = [0; 4];
+        for (x, y) in positions.iter() {
+            if let (Some(right), Some(bottom)) = (half(*x, self.width), half(*y, self.height)) {
+                quadrants[right + 2 * bottom] += 1;
+            }
+        }
+
+        quadrants.iter().product()
+    }
+
+    /// Robots per cell, row by row.
+    pub fn density(&self, positions: &[(i64, i64)]) -> Vec<Vec<usize>> {
+        let mut counts = vec![vec![0; self.width as usize]; self.height as usize];
+        for (x, y) in positions.iter() {
+            counts[*y as usize][*x as usize] += 1;
+        }
+        counts
+    }
+
+    /// Draws the room like the puzzle does, with the number of robots on each cell or `.` if there are none.
+    pub fn render(&self, positions: &[(i64, i64)]) -> String {
+        let mut out = String::new();
+        for row in self.density(positions) {
+            for count in row {
+                match count {
+                    0 => out.push('.'),
+                    1..=9 => out.push_str(&count.to_string()),
+                    _ => out.push('+'),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn print_positions(robots: &[Robot], room: Room, t: i64) {
+    let positions = room.positions_at(robots, t);
+    print!("{}", room.render(&positions));
+    println!(
+        "After {t} seconds in a {}x{} room, safety factor {}",
+        room.width,
+        room.height,
+        room.safety_factor(&positions)
+    );
+}